@@ -1,6 +1,9 @@
+pub mod accountdump;
+pub mod bench;
 pub mod bytecode;
 pub mod eofvalidation;
 pub mod evmrunner;
+pub mod runtx;
 pub mod statetest;
 
 use clap::Parser;
@@ -17,6 +20,12 @@ pub enum MainCmd {
     Evm(evmrunner::Cmd),
     /// Print the structure of an EVM bytecode.
     Bytecode(bytecode::Cmd),
+    /// Print the state of an account as JSON.
+    AccountDump(accountdump::Cmd),
+    /// Measure the opcode throughput of an EVM bytecode.
+    Bench(bench::Cmd),
+    /// Execute a transaction given as JSON and print the result as JSON.
+    RunTx(runtx::Cmd),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -25,6 +34,12 @@ pub enum Error {
     Statetest(#[from] statetest::Error),
     #[error(transparent)]
     EvmRunnerErrors(#[from] evmrunner::Errors),
+    #[error(transparent)]
+    AccountDump(#[from] accountdump::Errors),
+    #[error(transparent)]
+    Bench(#[from] bench::Errors),
+    #[error(transparent)]
+    RunTx(#[from] runtx::Errors),
     #[error("Eof validation failed: {:?}/{total_tests}", total_tests-failed_test)]
     EofValidation {
         failed_test: usize,
@@ -44,6 +59,9 @@ impl MainCmd {
                 cmd.run();
                 Ok(())
             }
+            Self::AccountDump(cmd) => cmd.run().map_err(Into::into),
+            Self::Bench(cmd) => cmd.run().map_err(Into::into),
+            Self::RunTx(cmd) => cmd.run().map_err(Into::into),
         }
     }
 }
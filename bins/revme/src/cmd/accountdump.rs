@@ -0,0 +1,146 @@
+use crate::cmd::statetest::models::AccountInfo;
+use clap::Parser;
+use revm::{
+    db::{CacheDB, EmptyDB},
+    primitives::{keccak256, Address, Bytecode, Bytes, B256, U256},
+    Database,
+};
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::Error as IoError,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Errors {
+    #[error("Account {0} not found in the state")]
+    AccountNotFound(Address),
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// `account-dump` subcommand.
+///
+/// Prints the nonce, balance, code and storage of an account as JSON.
+#[derive(Parser, Debug)]
+pub struct Cmd {
+    /// Address of the account.
+    #[arg(long)]
+    address: Address,
+    /// Path to a JSON file with the state: a map of addresses to accounts with `balance`,
+    /// `code`, `nonce` and `storage`, as the `pre` section of state tests.
+    #[arg(long)]
+    state: PathBuf,
+}
+
+/// Account state printed by [`Cmd`].
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountDump {
+    pub address: Address,
+    pub nonce: u64,
+    pub balance: U256,
+    pub code_hash: B256,
+    pub code: Bytes,
+    /// Storage slots, sorted by key.
+    pub storage: BTreeMap<U256, U256>,
+}
+
+impl Cmd {
+    /// Run account dump command.
+    pub fn run(&self) -> Result<(), Errors> {
+        let dump = self.dump()?;
+        println!("{}", serde_json::to_string_pretty(&dump)?);
+        Ok(())
+    }
+
+    /// Loads the state and returns the state of the account.
+    pub fn dump(&self) -> Result<AccountDump, Errors> {
+        let mut db = load_state(&self.state)?;
+        let info = db
+            .basic(self.address)
+            .expect("cache db is infallible")
+            .ok_or(Errors::AccountNotFound(self.address))?;
+        let storage = db.accounts[&self.address]
+            .storage
+            .iter()
+            .map(|(slot, value)| (*slot, *value))
+            .collect();
+        Ok(AccountDump {
+            address: self.address,
+            nonce: info.nonce,
+            balance: info.balance,
+            code_hash: info.code_hash,
+            code: info.code.unwrap_or_default().original_bytes(),
+            storage,
+        })
+    }
+}
+
+/// Loads a state file in the format of the `pre` section of state tests into a database.
+pub fn load_state(path: &Path) -> Result<CacheDB<EmptyDB>, Errors> {
+    let state: BTreeMap<Address, AccountInfo> = serde_json::from_str(&fs::read_to_string(path)?)?;
+
+    let mut db = CacheDB::new(EmptyDB::default());
+    for (address, account) in state {
+        let info = revm::primitives::AccountInfo::new(
+            account.balance,
+            account.nonce,
+            keccak256(&account.code),
+            Bytecode::new_raw(account.code),
+        );
+        db.insert_account_info(address, info);
+        for (slot, value) in account.storage {
+            db.insert_account_storage(address, slot, value)
+                .expect("account is inserted");
+        }
+    }
+    Ok(db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::primitives::address;
+
+    #[test]
+    fn dumps_account_from_state_file() {
+        let address = address!("1000000000000000000000000000000000000001");
+        let dir = tempfile::tempdir().unwrap();
+        let state = dir.path().join("state.json");
+        fs::write(
+            &state,
+            r#"{
+                "0x1000000000000000000000000000000000000001": {
+                    "balance": "0x0a",
+                    "code": "0x6001600055",
+                    "nonce": "0x02",
+                    "storage": { "0x01": "0x2a" }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let cmd = Cmd { address, state };
+        let json = serde_json::to_value(cmd.dump().unwrap()).unwrap();
+        assert_eq!(json["address"], address.to_string().to_lowercase());
+        assert_eq!(json["nonce"], 2);
+        assert_eq!(json["balance"], "0xa");
+        assert_eq!(json["code"], "0x6001600055");
+        assert_eq!(
+            json["codeHash"],
+            keccak256([0x60, 0x01, 0x60, 0x00, 0x55]).to_string()
+        );
+        assert_eq!(json["storage"]["0x1"], "0x2a");
+
+        let missing = Cmd {
+            address: Address::ZERO,
+            state: cmd.state,
+        };
+        assert!(matches!(missing.dump(), Err(Errors::AccountNotFound(_))));
+    }
+}
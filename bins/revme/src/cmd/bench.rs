@@ -0,0 +1,136 @@
+use clap::Parser;
+use revm::{
+    db::BenchmarkDB,
+    inspector_handle_register,
+    inspectors::OpcodeBudgetInspector,
+    primitives::{address, hex, Address, Bytecode, BytecodeDecodeError, EthereumWiring, TxKind},
+    Evm,
+};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Errors {
+    #[error("Invalid bytecode")]
+    InvalidBytecode,
+    #[error("EVM Error")]
+    EVMError,
+    #[error(transparent)]
+    BytecodeDecodeError(#[from] BytecodeDecodeError),
+}
+
+/// `bench` subcommand.
+///
+/// Executes a bytecode a number of times and reports the opcode throughput.
+#[derive(Parser, Debug)]
+pub struct Cmd {
+    /// Hex-encoded EVM bytecode to be executed, with or without `0x` prefix.
+    #[arg(long)]
+    bytecode: String,
+    /// Number of times the bytecode is executed.
+    #[arg(long, default_value_t = 1000)]
+    iterations: u64,
+}
+
+/// Summary of a [`Cmd`] run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchReport {
+    /// Number of executions.
+    pub iterations: u64,
+    /// Opcodes executed by a single execution.
+    pub opcodes: u64,
+    /// Total time of all executions.
+    pub elapsed: Duration,
+}
+
+impl BenchReport {
+    /// Returns the number of opcodes executed over all iterations.
+    pub fn total_opcodes(&self) -> u64 {
+        self.iterations * self.opcodes
+    }
+
+    /// Returns the number of opcodes executed per second.
+    pub fn opcodes_per_sec(&self) -> f64 {
+        self.total_opcodes() as f64 / self.elapsed.as_secs_f64()
+    }
+
+    /// Returns the average time of an opcode in nanoseconds.
+    pub fn ns_per_op(&self) -> f64 {
+        self.elapsed.as_nanos() as f64 / self.total_opcodes() as f64
+    }
+}
+
+impl Cmd {
+    /// Run bench command.
+    pub fn run(&self) -> Result<(), Errors> {
+        let report = self.bench()?;
+        println!(
+            "{} iterations, {} opcodes each, {:?} total",
+            report.iterations, report.opcodes, report.elapsed
+        );
+        println!("{:.0} opcodes/sec", report.opcodes_per_sec());
+        println!("{:.2} ns/op", report.ns_per_op());
+        Ok(())
+    }
+
+    /// Executes the bytecode and measures the time of the executions.
+    ///
+    /// The opcodes are counted in an additional inspected execution, so that the timed
+    /// executions run without an inspector.
+    pub fn bench(&self) -> Result<BenchReport, Errors> {
+        const CALLER: Address = address!("0000000000000000000000000000000000000001");
+
+        let bytecode = hex::decode(self.bytecode.trim()).map_err(|_| Errors::InvalidBytecode)?;
+        let db = BenchmarkDB::new_bytecode(Bytecode::new_raw_checked(bytecode.into())?);
+
+        // BenchmarkDB is dummy state that implements Database trait.
+        // the bytecode is deployed at zero address.
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, OpcodeBudgetInspector>>::builder()
+            .with_db(db)
+            .with_external_context(OpcodeBudgetInspector::new(u64::MAX))
+            .modify_tx_env(|tx| {
+                tx.caller = CALLER;
+                tx.transact_to = TxKind::Call(Address::ZERO);
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().map_err(|_| Errors::EVMError)?;
+        let opcodes = u64::MAX - evm.context.external.remaining();
+
+        let mut evm = evm
+            .modify()
+            .reset_handler_with_external_context::<EthereumWiring<BenchmarkDB, ()>>()
+            .with_default_ext_ctx()
+            .build();
+        let timer = Instant::now();
+        for _ in 0..self.iterations {
+            evm.transact().map_err(|_| Errors::EVMError)?;
+        }
+        let elapsed = timer.elapsed();
+
+        Ok(BenchReport {
+            iterations: self.iterations,
+            opcodes,
+            elapsed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bench_trivial_bytecode() {
+        // PUSH1 1, PUSH1 2, ADD, STOP
+        let cmd = Cmd {
+            bytecode: "0x600160020100".to_string(),
+            iterations: 3,
+        };
+        let report = cmd.bench().unwrap();
+        assert_eq!(report.iterations, 3);
+        assert_eq!(report.opcodes, 4);
+        assert_eq!(report.total_opcodes(), 12);
+        assert!(report.opcodes_per_sec() > 0.0);
+        assert!(report.ns_per_op() > 0.0);
+    }
+}
@@ -0,0 +1,203 @@
+use super::accountdump::{self, load_state};
+use clap::Parser;
+use revm::{
+    db::{CacheDB, EmptyDB},
+    primitives::{Address, Bytes, EthereumWiring, HaltReason, ResultAndState, TxKind, B256, U256},
+    Database, Evm,
+};
+use serde::Deserialize;
+use std::{fs, io::Error as IoError, path::PathBuf};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Errors {
+    #[error("EVM Error: {0}")]
+    EVMError(String),
+    #[error(transparent)]
+    State(#[from] accountdump::Errors),
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// `run-tx` subcommand.
+///
+/// Executes a single transaction given as JSON and prints the result and the changed state
+/// as JSON.
+#[derive(Parser, Debug)]
+pub struct Cmd {
+    /// Path to a JSON file with the transaction.
+    ///
+    /// Fields are `from`, `to` (omitted for create), `value`, `data`, `gas`, `gasPrice`,
+    /// `nonce` (defaults to the nonce of `from`) and `env`, with the block fields `number`,
+    /// `coinbase`, `timestamp`, `gasLimit`, `baseFee`, `prevrandao` and `chainId`.
+    #[arg(long)]
+    input: PathBuf,
+    /// Path to a JSON file with the state, in the format of `account-dump`.
+    #[arg(long)]
+    state: PathBuf,
+}
+
+/// Transaction read by [`Cmd`].
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct TxInput {
+    from: Address,
+    #[serde(default)]
+    to: Option<Address>,
+    #[serde(default)]
+    value: U256,
+    #[serde(default)]
+    data: Bytes,
+    gas: u64,
+    #[serde(default)]
+    gas_price: U256,
+    #[serde(default)]
+    nonce: Option<u64>,
+    #[serde(default)]
+    env: EnvInput,
+}
+
+/// Block and chain fields of [`TxInput`], defaults are taken from [`revm::primitives::Env`].
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct EnvInput {
+    number: Option<U256>,
+    coinbase: Option<Address>,
+    timestamp: Option<U256>,
+    gas_limit: Option<U256>,
+    base_fee: Option<U256>,
+    prevrandao: Option<B256>,
+    chain_id: Option<u64>,
+}
+
+impl Cmd {
+    /// Run transaction command.
+    pub fn run(&self) -> Result<(), Errors> {
+        let out = self.execute()?;
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        Ok(())
+    }
+
+    /// Executes the transaction against the state, without committing it.
+    pub fn execute(&self) -> Result<ResultAndState<HaltReason>, Errors> {
+        let input: TxInput = serde_json::from_str(&fs::read_to_string(&self.input)?)?;
+        let mut db = load_state(&self.state)?;
+
+        let nonce = match input.nonce {
+            Some(nonce) => nonce,
+            None => db
+                .basic(input.from)
+                .expect("cache db is infallible")
+                .map_or(0, |account| account.nonce),
+        };
+
+        let env = input.env;
+        let mut evm = Evm::<EthereumWiring<CacheDB<EmptyDB>, ()>>::builder()
+            .with_db(db)
+            .with_default_ext_ctx()
+            .modify_cfg_env(|cfg| {
+                if let Some(chain_id) = env.chain_id {
+                    cfg.chain_id = chain_id;
+                }
+            })
+            .modify_block_env(|block| {
+                block.number = env.number.unwrap_or(block.number);
+                block.coinbase = env.coinbase.unwrap_or(block.coinbase);
+                block.timestamp = env.timestamp.unwrap_or(block.timestamp);
+                block.gas_limit = env.gas_limit.unwrap_or(block.gas_limit);
+                block.basefee = env.base_fee.unwrap_or(block.basefee);
+                block.prevrandao = env.prevrandao.or(block.prevrandao);
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = input.from;
+                tx.transact_to = input.to.map_or(TxKind::Create, TxKind::Call);
+                tx.value = input.value;
+                tx.data = input.data;
+                tx.gas_limit = input.gas;
+                tx.gas_price = input.gas_price;
+                tx.nonce = nonce;
+            })
+            .build();
+
+        evm.transact().map_err(|e| Errors::EVMError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::primitives::{address, keccak256};
+
+    const STATE: &str = r#"{
+        "0x1000000000000000000000000000000000000001": {
+            "balance": "0x0de0b6b3a7640000",
+            "code": "0x",
+            "nonce": "0x01",
+            "storage": {}
+        }
+    }"#;
+
+    fn execute(input: &str) -> serde_json::Value {
+        let dir = tempfile::tempdir().unwrap();
+        let state = dir.path().join("state.json");
+        let input_path = dir.path().join("input.json");
+        fs::write(&state, STATE).unwrap();
+        fs::write(&input_path, input).unwrap();
+
+        let out = Cmd {
+            input: input_path,
+            state,
+        }
+        .execute()
+        .unwrap();
+        serde_json::to_value(out).unwrap()
+    }
+
+    #[test]
+    fn transfer_moves_value() {
+        let from = address!("1000000000000000000000000000000000000001");
+        let to = address!("2000000000000000000000000000000000000002");
+        let out = execute(
+            r#"{
+                "from": "0x1000000000000000000000000000000000000001",
+                "to": "0x2000000000000000000000000000000000000002",
+                "value": "0x64",
+                "gas": 21000,
+                "env": { "baseFee": "0x0" }
+            }"#,
+        );
+
+        assert_eq!(out["result"]["Success"]["gas_used"], 21000);
+        let state = &out["state"];
+        assert_eq!(state[to.to_string()]["info"]["balance"], "0x64");
+        let balance: U256 =
+            serde_json::from_value(state[from.to_string()]["info"]["balance"].clone()).unwrap();
+        assert_eq!(
+            balance,
+            U256::from(1_000_000_000_000_000_000u64) - U256::from(100)
+        );
+        assert_eq!(state[from.to_string()]["info"]["nonce"], 2);
+    }
+
+    #[test]
+    fn create_deploys_code() {
+        let from = address!("1000000000000000000000000000000000000001");
+        // returns the single byte code `STOP`
+        let out = execute(
+            r#"{
+                "from": "0x1000000000000000000000000000000000000001",
+                "data": "0x60006000526001601ff3",
+                "gas": 100000,
+                "env": { "baseFee": "0x0" }
+            }"#,
+        );
+
+        let created = from.create(1).to_string().to_lowercase();
+        assert_eq!(out["result"]["Success"]["output"]["Create"][1], created);
+        assert_eq!(
+            out["state"][&created]["info"]["code_hash"],
+            keccak256([0x00]).to_string()
+        );
+    }
+}
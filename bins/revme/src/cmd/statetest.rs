@@ -32,6 +32,13 @@ pub struct Cmd {
     /// Keep going after a test failure.
     #[clap(long, alias = "no-fail-fast")]
     keep_going: bool,
+    /// Print the EIP-3155 trace of every executed test to stdout.
+    /// Implies `--single-thread`.
+    #[clap(long)]
+    trace: bool,
+    /// Only run the test with this name, as keyed in the test file.
+    #[clap(long)]
+    test_name: Option<String>,
 }
 
 impl Cmd {
@@ -46,6 +53,8 @@ impl Cmd {
                 self.json,
                 self.json_outcome,
                 self.keep_going,
+                self.trace,
+                self.test_name.clone(),
             )?
         }
         Ok(())
@@ -18,7 +18,7 @@ use revm::{
 use serde_json::json;
 use std::{
     fmt::Debug,
-    io::{stderr, stdout},
+    io::{stderr, stdout, Write},
     path::{Path, PathBuf},
     sync::{
         atomic::{AtomicBool, AtomicUsize, Ordering},
@@ -32,6 +32,9 @@ use walkdir::{DirEntry, WalkDir};
 type ExecEvmWiring<'a> = EthereumWiring<&'a mut State<EmptyDB>, ()>;
 type TraceEvmWiring<'a> = EthereumWiring<&'a mut State<EmptyDB>, TracerEip3155>;
 
+/// Creates the writer that the EIP-3155 trace of each executed test is written to.
+pub type TraceWriter = fn() -> Box<dyn Write>;
+
 #[derive(Debug, Error)]
 #[error("Test {name} failed: {kind}")]
 pub struct TestError {
@@ -255,8 +258,9 @@ fn check_evm_execution<EXT: Debug>(
 pub fn execute_test_suite(
     path: &Path,
     elapsed: &Arc<Mutex<Duration>>,
-    trace: bool,
+    trace: Option<TraceWriter>,
     print_json_outcome: bool,
+    test_name: Option<&str>,
 ) -> Result<(), TestError> {
     if skip_test(path) {
         return Ok(());
@@ -269,6 +273,10 @@ pub fn execute_test_suite(
     })?;
 
     for (name, unit) in suite.0 {
+        if test_name.is_some_and(|test_name| test_name != name) {
+            continue;
+        }
+
         // Create database and insert cache
         let mut cache_state = revm::CacheState::new(false);
         for (address, info) in unit.pre {
@@ -297,20 +305,6 @@ pub fn execute_test_suite(
         env.block.difficulty = unit.env.current_difficulty;
         // after the Merge prevrandao replaces mix_hash field in block and replaced difficulty opcode in EVM.
         env.block.prevrandao = unit.env.current_random;
-        // EIP-4844
-        if let Some(current_excess_blob_gas) = unit.env.current_excess_blob_gas {
-            env.block
-                .set_blob_excess_gas_and_price(current_excess_blob_gas.to());
-        } else if let (Some(parent_blob_gas_used), Some(parent_excess_blob_gas)) = (
-            unit.env.parent_blob_gas_used,
-            unit.env.parent_excess_blob_gas,
-        ) {
-            env.block
-                .set_blob_excess_gas_and_price(calc_excess_blob_gas(
-                    parent_blob_gas_used.to(),
-                    parent_excess_blob_gas.to(),
-                ));
-        }
 
         // tx env
         env.tx.caller = if let Some(address) = unit.transaction.sender {
@@ -352,6 +346,27 @@ pub fn execute_test_suite(
                 env.block.prevrandao = Some(B256::default());
             }
 
+            // EIP-4844, the excess blob gas and the blob gas price are set per spec.
+            let excess_blob_gas =
+                if let Some(current_excess_blob_gas) = unit.env.current_excess_blob_gas {
+                    Some(current_excess_blob_gas.to())
+                } else if let (Some(parent_blob_gas_used), Some(parent_excess_blob_gas)) = (
+                    unit.env.parent_blob_gas_used,
+                    unit.env.parent_excess_blob_gas,
+                ) {
+                    Some(calc_excess_blob_gas(
+                        parent_excess_blob_gas.to(),
+                        parent_blob_gas_used.to(),
+                        spec_id,
+                    ))
+                } else {
+                    None
+                };
+            if let Some(excess_blob_gas) = excess_blob_gas {
+                env.block
+                    .set_blob_excess_gas_and_price(excess_blob_gas, spec_id);
+            }
+
             for (index, test) in tests.into_iter().enumerate() {
                 env.tx.gas_limit = unit.transaction.gas_limit[test.indexes.gas].saturating_to();
 
@@ -397,13 +412,11 @@ pub fn execute_test_suite(
                     .build();
 
                 // do the deed
-                let (e, exec_result) = if trace {
+                let (e, exec_result) = if let Some(trace) = trace {
                     let mut evm = evm
                         .modify()
                         .reset_handler_with_external_context::<EthereumWiring<_, TracerEip3155>>()
-                        .with_external_context(
-                            TracerEip3155::new(Box::new(stderr())).without_summary(),
-                        )
+                        .with_external_context(TracerEip3155::new(trace()).without_summary())
                         .with_spec_id(spec_id)
                         .append_handler_register(inspector_handle_register)
                         .build();
@@ -447,7 +460,7 @@ pub fn execute_test_suite(
                 // print only once or
                 // if we are already in trace mode, just return error
                 static FAILED: AtomicBool = AtomicBool::new(false);
-                if trace || FAILED.swap(true, Ordering::SeqCst) {
+                if trace.is_some() || FAILED.swap(true, Ordering::SeqCst) {
                     return Err(e);
                 }
 
@@ -490,18 +503,28 @@ pub fn execute_test_suite(
 pub fn run(
     test_files: Vec<PathBuf>,
     mut single_thread: bool,
-    trace: bool,
+    json: bool,
     mut print_outcome: bool,
     keep_going: bool,
+    trace: bool,
+    test_name: Option<String>,
 ) -> Result<(), TestError> {
-    // trace implies print_outcome
-    if trace {
+    // json implies print_outcome
+    if json {
         print_outcome = true;
     }
     // print_outcome or trace implies single_thread
-    if print_outcome {
+    if print_outcome || trace {
         single_thread = true;
     }
+    // trace goes to stdout, the json trace to stderr next to the outcome
+    let trace: Option<TraceWriter> = if trace {
+        Some(|| Box::new(stdout()))
+    } else if json {
+        Some(|| Box::new(stderr()))
+    } else {
+        None
+    };
     let n_files = test_files.len();
 
     let n_errors = Arc::new(AtomicUsize::new(0));
@@ -523,6 +546,7 @@ pub fn run(
         let n_errors = n_errors.clone();
         let console_bar = console_bar.clone();
         let elapsed = elapsed.clone();
+        let test_name = test_name.clone();
 
         let thread = std::thread::Builder::new().name(format!("runner-{i}"));
 
@@ -541,7 +565,13 @@ pub fn run(
                 (prev_idx, test_path)
             };
 
-            let result = execute_test_suite(&test_path, &elapsed, trace, print_outcome);
+            let result = execute_test_suite(
+                &test_path,
+                &elapsed,
+                trace,
+                print_outcome,
+                test_name.as_deref(),
+            );
 
             // Increment after the test is done.
             console_bar.inc(1);
@@ -596,3 +626,88 @@ pub fn run(
         Err(thread_errors.swap_remove(0))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TRACE: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+    struct TestTrace;
+
+    impl Write for TestTrace {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            TRACE.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// `SSTORE(0, 1)` called from a funded sender.
+    const SUITE: &str = r#"{
+        "sstore": {
+            "env": {
+                "currentCoinbase": "0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba",
+                "currentGasLimit": "0x05f5e100",
+                "currentNumber": "0x01",
+                "currentTimestamp": "0x03e8",
+                "currentBaseFee": "0x0a",
+                "currentRandom": "0x0000000000000000000000000000000000000000000000000000000000020000"
+            },
+            "pre": {
+                "0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b": {
+                    "balance": "0x3b9aca00",
+                    "code": "0x",
+                    "nonce": "0x00",
+                    "storage": {}
+                },
+                "0x1000000000000000000000000000000000000000": {
+                    "balance": "0x00",
+                    "code": "0x600160005500",
+                    "nonce": "0x00",
+                    "storage": {}
+                }
+            },
+            "post": {
+                "Cancun": [
+                    {
+                        "hash": "0x3fd575da218b7789119e1f131e99e4f827259e82228ab5c21a5e863a07ec534a",
+                        "logs": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+                        "indexes": { "data": 0, "gas": 0, "value": 0 }
+                    }
+                ]
+            },
+            "transaction": {
+                "data": ["0x"],
+                "gasLimit": ["0x0186a0"],
+                "gasPrice": "0x0a",
+                "nonce": "0x00",
+                "secretKey": "0x45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8",
+                "to": "0x1000000000000000000000000000000000000000",
+                "value": ["0x00"]
+            }
+        }
+    }"#;
+
+    #[test]
+    fn trace_runs_selected_test() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("suite.json");
+        std::fs::write(&path, SUITE).unwrap();
+        let elapsed = Arc::new(Mutex::new(Duration::ZERO));
+        let trace: TraceWriter = || Box::new(TestTrace);
+
+        execute_test_suite(&path, &elapsed, Some(trace), false, Some("other")).unwrap();
+        assert!(TRACE.lock().unwrap().is_empty());
+
+        execute_test_suite(&path, &elapsed, Some(trace), false, Some("sstore")).unwrap();
+        let trace = String::from_utf8(TRACE.lock().unwrap().clone()).unwrap();
+        assert!(!trace.is_empty());
+        assert!(trace
+            .lines()
+            .any(|line| line.contains(r#""opName":"SSTORE""#)));
+    }
+}
@@ -6,6 +6,8 @@ mod constants;
 pub use calc::*;
 pub use constants::*;
 
+use crate::primitives::SpecId;
+
 /// Represents the state of gas during execution.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -77,12 +79,41 @@ impl Gas {
         self.remaining - self.remaining / 64
     }
 
+    /// Returns the gas forwarded to a call that requested `requested` gas with `remaining` gas left
+    /// in the caller.
+    ///
+    /// Since EIP-150 (Tangerine Whistle) the forwarded gas is capped at all but one 64th of the
+    /// remaining gas. Before that the requested gas is forwarded as is.
+    #[inline]
+    pub const fn call_gas_limit(requested: u64, remaining: u64, spec: SpecId) -> u64 {
+        if !spec.is_enabled_in(SpecId::TANGERINE) {
+            return requested;
+        }
+        let cap = remaining - remaining / 64;
+        if requested < cap {
+            requested
+        } else {
+            cap
+        }
+    }
+
     /// Erases a gas cost from the totals.
     #[inline]
     pub fn erase_cost(&mut self, returned: u64) {
         self.remaining += returned;
     }
 
+    /// Merges the gas of a successfully returned child frame: its unused gas is given back and
+    /// its refund is absorbed.
+    ///
+    /// A reverted child only gives back its unused gas, see [`Self::erase_cost`]; its refund
+    /// is discarded.
+    #[inline]
+    pub fn merge_child(&mut self, child: &Gas) {
+        self.erase_cost(child.remaining());
+        self.record_refund(child.refunded());
+    }
+
     /// Spends all remaining gas.
     #[inline]
     pub fn spend_all(&mut self) {
@@ -129,3 +160,75 @@ impl Gas {
         success
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spent_and_remaining_sum_to_limit() {
+        let mut gas = Gas::new(100);
+        assert!(gas.record_cost(30));
+        assert!(gas.record_cost(25));
+        gas.record_refund(5);
+
+        assert_eq!(gas.spent(), 55);
+        assert_eq!(gas.remaining(), 45);
+        assert_eq!(gas.spent() + gas.remaining(), gas.limit());
+        assert_eq!(gas.refunded(), 5);
+    }
+
+    #[test]
+    fn merge_child_reclaims_unused_gas_and_refund() {
+        let mut parent = Gas::new(100_000);
+        let forwarded = 40_000;
+        assert!(parent.record_cost(forwarded));
+
+        let mut child = Gas::new(forwarded);
+        assert!(child.record_cost(forwarded / 2));
+        child.record_refund(4_800);
+
+        parent.merge_child(&child);
+        assert_eq!(parent.remaining(), 60_000 + forwarded / 2);
+        assert_eq!(parent.spent(), forwarded / 2);
+        assert_eq!(parent.refunded(), 4_800);
+    }
+
+    #[test]
+    fn call_gas_limit_applies_63_of_64_rule_since_tangerine() {
+        let remaining = 64_000;
+        let requested = 100_000;
+
+        assert_eq!(
+            Gas::call_gas_limit(requested, remaining, SpecId::HOMESTEAD),
+            requested
+        );
+        assert_eq!(
+            Gas::call_gas_limit(requested, remaining, SpecId::BERLIN),
+            63_000
+        );
+        assert_eq!(Gas::call_gas_limit(1_000, remaining, SpecId::BERLIN), 1_000);
+        assert_eq!(
+            Gas::call_gas_limit(requested, remaining, SpecId::BERLIN),
+            Gas::new(remaining).remaining_63_of_64_parts()
+        );
+    }
+
+    #[test]
+    fn final_refund_is_capped_by_refund_quotient() {
+        assert_eq!(refund_quotient(SpecId::BERLIN), 2);
+        assert_eq!(refund_quotient(SpecId::LONDON), 5);
+
+        let mut gas = Gas::new(100_000);
+        assert!(gas.record_cost(50_000));
+        gas.record_refund(40_000);
+
+        let mut berlin = gas;
+        berlin.set_final_refund(false);
+        assert_eq!(berlin.refunded(), 25_000);
+
+        let mut london = gas;
+        london.set_final_refund(true);
+        assert_eq!(london.refunded(), 10_000);
+    }
+}
@@ -3,7 +3,7 @@ use revm_primitives::eip7702;
 use super::constants::*;
 use crate::{
     num_words,
-    primitives::{AccessListItem, SpecId, U256},
+    primitives::{AccessGasCosts, AccessListItem, SpecId, U256},
     AccountLoad, Eip7702CodeLoad, SStoreResult, SelfDestructResult, StateLoad,
 };
 
@@ -18,13 +18,23 @@ macro_rules! tri {
 }
 
 /// `SSTORE` opcode refund calculation.
-#[allow(clippy::collapsible_else_if)]
 #[inline]
 pub fn sstore_refund(spec_id: SpecId, vals: &SStoreResult) -> i64 {
+    sstore_refund_with_costs(spec_id, vals, &AccessGasCosts::MAINNET)
+}
+
+/// [`sstore_refund`] with the given access costs instead of [`AccessGasCosts::MAINNET`].
+#[allow(clippy::collapsible_else_if)]
+#[inline]
+pub fn sstore_refund_with_costs(
+    spec_id: SpecId,
+    vals: &SStoreResult,
+    costs: &AccessGasCosts,
+) -> i64 {
     if spec_id.is_enabled_in(SpecId::ISTANBUL) {
         // EIP-3529: Reduction in refunds
         let sstore_clears_schedule = if spec_id.is_enabled_in(SpecId::LONDON) {
-            (SSTORE_RESET - COLD_SLOAD_COST + ACCESS_LIST_STORAGE_KEY) as i64
+            (warm_sstore_reset(costs) + ACCESS_LIST_STORAGE_KEY) as i64
         } else {
             REFUND_SSTORE_CLEARS
         };
@@ -46,14 +56,14 @@ pub fn sstore_refund(spec_id: SpecId, vals: &SStoreResult) -> i64 {
 
                 if vals.is_original_eq_new() {
                     let (gas_sstore_reset, gas_sload) = if spec_id.is_enabled_in(SpecId::BERLIN) {
-                        (SSTORE_RESET - COLD_SLOAD_COST, WARM_STORAGE_READ_COST)
+                        (warm_sstore_reset(costs), costs.warm_sload)
                     } else {
-                        (SSTORE_RESET, sload_cost(spec_id, false))
+                        (SSTORE_RESET, sload_cost_with_costs(spec_id, false, costs))
                     };
                     if vals.is_original_zero() {
-                        refund += (SSTORE_SET - gas_sload) as i64;
+                        refund += SSTORE_SET.saturating_sub(gas_sload) as i64;
                     } else {
-                        refund += (gas_sstore_reset - gas_sload) as i64;
+                        refund += gas_sstore_reset.saturating_sub(gas_sload) as i64;
                     }
                 }
 
@@ -69,6 +79,18 @@ pub fn sstore_refund(spec_id: SpecId, vals: &SStoreResult) -> i64 {
     }
 }
 
+/// Divisor of the spent gas that caps the final gas refund.
+///
+/// EIP-3529: Reduction in refunds, changes it from 2 to 5 in London.
+#[inline]
+pub const fn refund_quotient(spec_id: SpecId) -> u64 {
+    if spec_id.is_enabled_in(SpecId::LONDON) {
+        5
+    } else {
+        2
+    }
+}
+
 /// `CREATE2` opcode cost calculation.
 #[inline]
 pub const fn create2_cost(len: u64) -> Option<u64> {
@@ -126,8 +148,19 @@ pub const fn verylowcopy_cost(len: u64) -> Option<u64> {
 /// `EXTCODECOPY` opcode cost calculation.
 #[inline]
 pub const fn extcodecopy_cost(spec_id: SpecId, len: u64, load: Eip7702CodeLoad<()>) -> Option<u64> {
+    extcodecopy_cost_with_costs(spec_id, len, load, &AccessGasCosts::MAINNET)
+}
+
+/// [`extcodecopy_cost`] with the given access costs instead of [`AccessGasCosts::MAINNET`].
+#[inline]
+pub const fn extcodecopy_cost_with_costs(
+    spec_id: SpecId,
+    len: u64,
+    load: Eip7702CodeLoad<()>,
+    costs: &AccessGasCosts,
+) -> Option<u64> {
     let base_gas = if spec_id.is_enabled_in(SpecId::BERLIN) {
-        warm_cold_cost_with_delegation(load)
+        warm_cold_cost_with_delegation_with_costs(load, costs)
     } else if spec_id.is_enabled_in(SpecId::TANGERINE) {
         700
     } else {
@@ -170,11 +203,17 @@ pub const fn initcode_cost(len: u64) -> u64 {
 /// `SLOAD` opcode cost calculation.
 #[inline]
 pub const fn sload_cost(spec_id: SpecId, is_cold: bool) -> u64 {
+    sload_cost_with_costs(spec_id, is_cold, &AccessGasCosts::MAINNET)
+}
+
+/// [`sload_cost`] with the given access costs instead of [`AccessGasCosts::MAINNET`].
+#[inline]
+pub const fn sload_cost_with_costs(spec_id: SpecId, is_cold: bool, costs: &AccessGasCosts) -> u64 {
     if spec_id.is_enabled_in(SpecId::BERLIN) {
         if is_cold {
-            COLD_SLOAD_COST
+            costs.cold_sload
         } else {
-            WARM_STORAGE_READ_COST
+            costs.warm_sload
         }
     } else if spec_id.is_enabled_in(SpecId::ISTANBUL) {
         // EIP-1884: Repricing for trie-size-dependent opcodes
@@ -188,8 +227,22 @@ pub const fn sload_cost(spec_id: SpecId, is_cold: bool) -> u64 {
 }
 
 /// `SSTORE` opcode cost calculation.
+///
+/// Returns `None` if `gas`, the gas left, is at or below the call stipend since Istanbul.
 #[inline]
 pub fn sstore_cost(spec_id: SpecId, vals: &SStoreResult, gas: u64, is_cold: bool) -> Option<u64> {
+    sstore_cost_with_costs(spec_id, vals, gas, is_cold, &AccessGasCosts::MAINNET)
+}
+
+/// [`sstore_cost`] with the given access costs instead of [`AccessGasCosts::MAINNET`].
+#[inline]
+pub fn sstore_cost_with_costs(
+    spec_id: SpecId,
+    vals: &SStoreResult,
+    gas: u64,
+    is_cold: bool,
+    costs: &AccessGasCosts,
+) -> Option<u64> {
     // EIP-1706 Disable SSTORE with gasleft lower than call stipend
     if spec_id.is_enabled_in(SpecId::ISTANBUL) && gas <= CALL_STIPEND {
         return None;
@@ -197,16 +250,18 @@ pub fn sstore_cost(spec_id: SpecId, vals: &SStoreResult, gas: u64, is_cold: bool
 
     if spec_id.is_enabled_in(SpecId::BERLIN) {
         // Berlin specification logic
-        let mut gas_cost = istanbul_sstore_cost::<WARM_STORAGE_READ_COST, WARM_SSTORE_RESET>(vals);
+        let mut gas_cost = istanbul_sstore_cost(vals, costs.warm_sload, warm_sstore_reset(costs));
 
         if is_cold {
-            gas_cost += COLD_SLOAD_COST;
+            gas_cost += costs.cold_sload;
         }
         Some(gas_cost)
     } else if spec_id.is_enabled_in(SpecId::ISTANBUL) {
         // Istanbul logic
-        Some(istanbul_sstore_cost::<INSTANBUL_SLOAD_GAS, SSTORE_RESET>(
+        Some(istanbul_sstore_cost(
             vals,
+            INSTANBUL_SLOAD_GAS,
+            SSTORE_RESET,
         ))
     } else {
         // Frontier logic
@@ -214,19 +269,25 @@ pub fn sstore_cost(spec_id: SpecId, vals: &SStoreResult, gas: u64, is_cold: bool
     }
 }
 
+/// Cost of resetting a warm slot since Berlin, `SSTORE_RESET` minus the cold `SLOAD` cost.
+///
+/// Equals [`WARM_SSTORE_RESET`] under [`AccessGasCosts::MAINNET`].
+#[inline]
+const fn warm_sstore_reset(costs: &AccessGasCosts) -> u64 {
+    SSTORE_RESET.saturating_sub(costs.cold_sload)
+}
+
 /// EIP-2200: Structured Definitions for Net Gas Metering
 #[inline]
-fn istanbul_sstore_cost<const SLOAD_GAS: u64, const SSTORE_RESET_GAS: u64>(
-    vals: &SStoreResult,
-) -> u64 {
+fn istanbul_sstore_cost(vals: &SStoreResult, sload_gas: u64, sstore_reset_gas: u64) -> u64 {
     if vals.is_new_eq_present() {
-        SLOAD_GAS
+        sload_gas
     } else if vals.is_original_eq_present() && vals.is_original_zero() {
         SSTORE_SET
     } else if vals.is_original_eq_present() {
-        SSTORE_RESET_GAS
+        sstore_reset_gas
     } else {
-        SLOAD_GAS
+        sload_gas
     }
 }
 
@@ -243,6 +304,16 @@ fn frontier_sstore_cost(vals: &SStoreResult) -> u64 {
 /// `SELFDESTRUCT` opcode cost calculation.
 #[inline]
 pub const fn selfdestruct_cost(spec_id: SpecId, res: StateLoad<SelfDestructResult>) -> u64 {
+    selfdestruct_cost_with_costs(spec_id, res, &AccessGasCosts::MAINNET)
+}
+
+/// [`selfdestruct_cost`] with the given access costs instead of [`AccessGasCosts::MAINNET`].
+#[inline]
+pub const fn selfdestruct_cost_with_costs(
+    spec_id: SpecId,
+    res: StateLoad<SelfDestructResult>,
+    costs: &AccessGasCosts,
+) -> u64 {
     // EIP-161: State trie clearing (invariant-preserving alternative)
     let should_charge_topup = if spec_id.is_enabled_in(SpecId::SPURIOUS_DRAGON) {
         res.data.had_value && !res.data.target_exists
@@ -267,7 +338,7 @@ pub const fn selfdestruct_cost(spec_id: SpecId, res: StateLoad<SelfDestructResul
 
     let mut gas = selfdestruct_gas + selfdestruct_gas_topup;
     if spec_id.is_enabled_in(SpecId::BERLIN) && res.is_cold {
-        gas += COLD_ACCOUNT_ACCESS_COST
+        gas += costs.cold_account_access
     }
     gas
 }
@@ -294,9 +365,25 @@ pub const fn selfdestruct_cost(spec_id: SpecId, res: StateLoad<SelfDestructResul
 /// as they were present before SPURIOUS_DRAGON hardfork.
 #[inline]
 pub const fn call_cost(spec_id: SpecId, transfers_value: bool, account_load: AccountLoad) -> u64 {
+    call_cost_with_costs(
+        spec_id,
+        transfers_value,
+        account_load,
+        &AccessGasCosts::MAINNET,
+    )
+}
+
+/// [`call_cost`] with the given access costs instead of [`AccessGasCosts::MAINNET`].
+#[inline]
+pub const fn call_cost_with_costs(
+    spec_id: SpecId,
+    transfers_value: bool,
+    account_load: AccountLoad,
+    costs: &AccessGasCosts,
+) -> u64 {
     // Account access.
     let mut gas = if spec_id.is_enabled_in(SpecId::BERLIN) {
-        warm_cold_cost_with_delegation(account_load.load)
+        warm_cold_cost_with_delegation_with_costs(account_load.load, costs)
     } else if spec_id.is_enabled_in(SpecId::TANGERINE) {
         // EIP-150: Gas cost changes for IO-heavy operations
         700
@@ -328,10 +415,16 @@ pub const fn call_cost(spec_id: SpecId, transfers_value: bool, account_load: Acc
 /// Berlin warm and cold storage access cost for account access.
 #[inline]
 pub const fn warm_cold_cost(is_cold: bool) -> u64 {
+    warm_cold_cost_with_costs(is_cold, &AccessGasCosts::MAINNET)
+}
+
+/// [`warm_cold_cost`] with the given access costs instead of [`AccessGasCosts::MAINNET`].
+#[inline]
+pub const fn warm_cold_cost_with_costs(is_cold: bool, costs: &AccessGasCosts) -> u64 {
     if is_cold {
-        COLD_ACCOUNT_ACCESS_COST
+        costs.cold_account_access
     } else {
-        WARM_STORAGE_READ_COST
+        costs.warm_account_access
     }
 }
 
@@ -340,9 +433,18 @@ pub const fn warm_cold_cost(is_cold: bool) -> u64 {
 /// If delegation is Some, add additional cost for delegation account load.
 #[inline]
 pub const fn warm_cold_cost_with_delegation(load: Eip7702CodeLoad<()>) -> u64 {
-    let mut gas = warm_cold_cost(load.state_load.is_cold);
+    warm_cold_cost_with_delegation_with_costs(load, &AccessGasCosts::MAINNET)
+}
+
+/// [`warm_cold_cost_with_delegation`] with the given access costs instead of [`AccessGasCosts::MAINNET`].
+#[inline]
+pub const fn warm_cold_cost_with_delegation_with_costs(
+    load: Eip7702CodeLoad<()>,
+    costs: &AccessGasCosts,
+) -> u64 {
+    let mut gas = warm_cold_cost_with_costs(load.state_load.is_cold, costs);
     if let Some(is_cold) = load.is_delegate_account_cold {
-        gas += warm_cold_cost(is_cold);
+        gas += warm_cold_cost_with_costs(is_cold, costs);
     }
     gas
 }
@@ -397,10 +499,10 @@ pub fn validate_initial_tx_gas(
             // EIP-2: Homestead Hard-fork Changes
             53000
         } else {
-            21000
+            TRANSACTION_BASE
         }
     } else {
-        21000
+        TRANSACTION_BASE
     };
 
     // EIP-3860: Limit and meter initcode
@@ -416,3 +518,70 @@ pub fn validate_initial_tx_gas(
 
     initial_gas
 }
+
+/// Returns the number of calldata tokens of EIP-7623: one per zero byte and
+/// [`STANDARD_TOKEN_COST`] per non-zero byte.
+#[inline]
+pub fn calc_calldata_tokens(data: &[u8]) -> u64 {
+    let zero_data_len = data.iter().filter(|v| **v == 0).count() as u64;
+    let non_zero_data_len = data.len() as u64 - zero_data_len;
+    zero_data_len + non_zero_data_len * STANDARD_TOKEN_COST
+}
+
+/// Returns the EIP-7623 floor of the gas used by a transaction with `tokens` calldata tokens,
+/// see [`calc_calldata_tokens`].
+#[inline]
+pub const fn calc_7623_floor_gas(tokens: u64) -> u64 {
+    tokens
+        .saturating_mul(TOTAL_COST_FLOOR_PER_TOKEN)
+        .saturating_add(TRANSACTION_BASE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eip7623_floor_of_mixed_calldata() {
+        assert_eq!(calc_calldata_tokens(&[]), 0);
+        assert_eq!(calc_calldata_tokens(&[0, 0, 1, 0xff]), 10);
+        assert_eq!(calc_calldata_tokens(&[0; 100]), 100);
+        assert_eq!(calc_calldata_tokens(&[1; 100]), 400);
+
+        assert_eq!(calc_7623_floor_gas(0), 21000);
+        assert_eq!(calc_7623_floor_gas(10), 21100);
+        assert_eq!(calc_7623_floor_gas(400), 25000);
+    }
+
+    #[test]
+    fn sstore_restore_nets_two_warm_reads_under_custom_costs() {
+        let costs = AccessGasCosts {
+            cold_sload: 1000,
+            warm_sload: 50,
+            ..AccessGasCosts::MAINNET
+        };
+        let one = U256::from(1);
+        let two = U256::from(2);
+
+        for original in [U256::ZERO, one] {
+            let set = SStoreResult {
+                original_value: original,
+                present_value: original,
+                new_value: two,
+            };
+            let restore = SStoreResult {
+                original_value: original,
+                present_value: two,
+                new_value: original,
+            };
+
+            let charged = sstore_cost_with_costs(SpecId::CANCUN, &set, 100_000, false, &costs)
+                .unwrap()
+                + sstore_cost_with_costs(SpecId::CANCUN, &restore, 100_000, false, &costs).unwrap();
+            let refunded = sstore_refund_with_costs(SpecId::CANCUN, &set, &costs)
+                + sstore_refund_with_costs(SpecId::CANCUN, &restore, &costs);
+
+            assert_eq!(refunded, charged as i64 - 2 * costs.warm_sload as i64);
+        }
+    }
+}
@@ -33,6 +33,8 @@ pub const SSTORE_SET: u64 = 20000;
 pub const SSTORE_RESET: u64 = 5000;
 pub const REFUND_SSTORE_CLEARS: i64 = 15000;
 
+/// Base stipend of a transaction that does not create a contract.
+pub const TRANSACTION_BASE: u64 = 21000;
 pub const TRANSACTION_ZERO_DATA: u64 = 4;
 pub const TRANSACTION_NON_ZERO_DATA_INIT: u64 = 16;
 pub const TRANSACTION_NON_ZERO_DATA_FRONTIER: u64 = 68;
@@ -50,5 +52,11 @@ pub const WARM_SSTORE_RESET: u64 = SSTORE_RESET - COLD_SLOAD_COST;
 /// EIP-3860 : Limit and meter initcode
 pub const INITCODE_WORD_COST: u64 = 2;
 
+/// EIP-7623: Increase calldata cost
+pub const STANDARD_TOKEN_COST: u64 = 4;
+/// Gas charged per calldata token by the floor, see
+/// [`calc_7623_floor_gas`](super::calc_7623_floor_gas).
+pub const TOTAL_COST_FLOOR_PER_TOKEN: u64 = 10;
+
 pub const CALL_STIPEND: u64 = 2300;
 pub const MIN_CALLEE_GAS: u64 = CALL_STIPEND;
@@ -3,7 +3,7 @@ use core::ops::{Deref, DerefMut};
 
 mod dummy;
 pub use dummy::DummyHost;
-use revm_primitives::{EnvWiring, EvmWiring};
+use revm_primitives::{Block, EnvWiring, EvmWiring};
 
 /// EVM context host.
 pub trait Host {
@@ -19,6 +19,18 @@ pub trait Host {
     /// Load an account code.
     fn load_account_delegated(&mut self, address: Address) -> Option<AccountLoad>;
 
+    /// Returns the blob base fee of the block, as pushed by `BLOBBASEFEE`.
+    ///
+    /// Defaults to the price derived from the `excess_blob_gas` of the block, zero if it is
+    /// not set. See [EIP-7516](https://eips.ethereum.org/EIPS/eip-7516).
+    fn blob_gasprice(&self) -> U256 {
+        self.env()
+            .block
+            .get_blob_gasprice()
+            .map(|price| U256::from(*price))
+            .unwrap_or_default()
+    }
+
     /// Get the block hash of the given block `number`.
     fn block_hash(&mut self, number: u64) -> Option<B256>;
 
@@ -29,6 +41,11 @@ pub trait Host {
     fn code(&mut self, address: Address) -> Option<Eip7702CodeLoad<Bytes>>;
 
     /// Get code hash of `address` and if the account is cold.
+    ///
+    /// The hash is zero for accounts that are empty by EIP-161 and [`KECCAK_EMPTY`] for other
+    /// accounts without code, as returned by `EXTCODEHASH`.
+    ///
+    /// [`KECCAK_EMPTY`]: crate::primitives::KECCAK_EMPTY
     fn code_hash(&mut self, address: Address) -> Option<Eip7702CodeLoad<B256>>;
 
     /// Get storage value of `address` at `index` and if the account is cold.
@@ -51,7 +68,18 @@ pub trait Host {
     fn tstore(&mut self, address: Address, index: U256, value: U256);
 
     /// Emit a log owned by `address` with given `LogData`.
-    fn log(&mut self, log: Log);
+    ///
+    /// Returns `false` without emitting the log if the transaction already emitted
+    /// [`CfgEnv::max_logs`](crate::primitives::CfgEnv::max_logs) logs.
+    fn log(&mut self, log: Log) -> bool;
+
+    /// Returns the number of logs emitted so far by the transaction, excluding logs of
+    /// reverted frames.
+    ///
+    /// Defaults to zero for hosts that do not keep the emitted logs.
+    fn log_count(&self) -> u64 {
+        0
+    }
 
     /// Mark `address` to be deleted, with funds transferred to `target`.
     fn selfdestruct(
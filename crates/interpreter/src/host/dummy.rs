@@ -130,8 +130,19 @@ where
     }
 
     #[inline]
-    fn log(&mut self, log: Log) {
-        self.log.push(log)
+    fn log(&mut self, log: Log) -> bool {
+        if let Some(max_logs) = self.env.cfg.max_logs {
+            if self.log_count() >= max_logs {
+                return false;
+            }
+        }
+        self.log.push(log);
+        true
+    }
+
+    #[inline]
+    fn log_count(&self) -> u64 {
+        self.log.len() as u64
     }
 
     #[inline]
@@ -51,6 +51,8 @@ pub enum InstructionResult {
     PrecompileOOG,
     /// Out of gas error encountered while calling an invalid operand.
     InvalidOperandOOG,
+    /// `SSTORE` executed with gas left at or below the call stipend, see EIP-2200.
+    ReentrancySentryOOG,
     /// Unknown or invalid opcode.
     OpcodeNotFound,
     /// Invalid `CALL` with value transfer in static context.
@@ -97,6 +99,12 @@ pub enum InstructionResult {
     EofAuxDataTooSmall,
     /// `EXT*CALL` target address needs to be padded with 0s.
     InvalidEXTCALLTarget,
+    /// Execution exceeded the opcode budget set by an inspector.
+    OpcodeBudgetExceeded,
+    /// Transaction emitted more logs than allowed by [`CfgEnv::max_logs`](crate::primitives::CfgEnv::max_logs).
+    LogLimitExceeded,
+    /// Execution ran past the deadline set in `CfgEnv::deadline`.
+    DeadlineExceeded,
 }
 
 impl From<SuccessReason> for InstructionResult {
@@ -119,6 +127,7 @@ impl From<HaltReason> for InstructionResult {
                 OutOfGasError::Memory => Self::MemoryOOG,
                 OutOfGasError::MemoryLimit => Self::MemoryLimitOOG,
                 OutOfGasError::Precompile => Self::PrecompileOOG,
+                OutOfGasError::ReentrancySentry => Self::ReentrancySentryOOG,
             },
             HaltReason::OpcodeNotFound => Self::OpcodeNotFound,
             HaltReason::InvalidFEOpcode => Self::InvalidFEOpcode,
@@ -142,6 +151,9 @@ impl From<HaltReason> for InstructionResult {
             HaltReason::EofAuxDataTooSmall => Self::EofAuxDataTooSmall,
             HaltReason::EOFFunctionStackOverflow => Self::EOFFunctionStackOverflow,
             HaltReason::InvalidEXTCALLTarget => Self::InvalidEXTCALLTarget,
+            HaltReason::OpcodeBudgetExceeded => Self::OpcodeBudgetExceeded,
+            HaltReason::LogLimitExceeded => Self::LogLimitExceeded,
+            HaltReason::DeadlineExceeded => Self::DeadlineExceeded,
         }
     }
 }
@@ -177,6 +189,7 @@ macro_rules! return_error {
             | InstructionResult::MemoryLimitOOG
             | InstructionResult::PrecompileOOG
             | InstructionResult::InvalidOperandOOG
+            | InstructionResult::ReentrancySentryOOG
             | InstructionResult::OpcodeNotFound
             | InstructionResult::CallNotAllowedInsideStatic
             | InstructionResult::StateChangeDuringStaticCall
@@ -200,6 +213,9 @@ macro_rules! return_error {
             | InstructionResult::EofAuxDataTooSmall
             | InstructionResult::EofAuxDataOverflow
             | InstructionResult::InvalidEXTCALLTarget
+            | InstructionResult::OpcodeBudgetExceeded
+            | InstructionResult::LogLimitExceeded
+            | InstructionResult::DeadlineExceeded
     };
 }
 
@@ -221,6 +237,64 @@ impl InstructionResult {
     pub const fn is_error(self) -> bool {
         matches!(self, return_error!())
     }
+
+    /// Returns the name of the result, e.g. `"OutOfGas"` or `"InvalidJump"`.
+    ///
+    /// The name is the variant name and is stable across releases.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Continue => "Continue",
+            Self::Stop => "Stop",
+            Self::Return => "Return",
+            Self::SelfDestruct => "SelfDestruct",
+            Self::ReturnContract => "ReturnContract",
+            Self::Revert => "Revert",
+            Self::CallTooDeep => "CallTooDeep",
+            Self::OutOfFunds => "OutOfFunds",
+            Self::CreateInitCodeStartingEF00 => "CreateInitCodeStartingEF00",
+            Self::InvalidEOFInitCode => "InvalidEOFInitCode",
+            Self::InvalidExtDelegateCallTarget => "InvalidExtDelegateCallTarget",
+            Self::CallOrCreate => "CallOrCreate",
+            Self::OutOfGas => "OutOfGas",
+            Self::MemoryOOG => "MemoryOOG",
+            Self::MemoryLimitOOG => "MemoryLimitOOG",
+            Self::PrecompileOOG => "PrecompileOOG",
+            Self::InvalidOperandOOG => "InvalidOperandOOG",
+            Self::ReentrancySentryOOG => "ReentrancySentryOOG",
+            Self::OpcodeNotFound => "OpcodeNotFound",
+            Self::CallNotAllowedInsideStatic => "CallNotAllowedInsideStatic",
+            Self::StateChangeDuringStaticCall => "StateChangeDuringStaticCall",
+            Self::InvalidFEOpcode => "InvalidFEOpcode",
+            Self::InvalidJump => "InvalidJump",
+            Self::NotActivated => "NotActivated",
+            Self::StackUnderflow => "StackUnderflow",
+            Self::StackOverflow => "StackOverflow",
+            Self::OutOfOffset => "OutOfOffset",
+            Self::CreateCollision => "CreateCollision",
+            Self::OverflowPayment => "OverflowPayment",
+            Self::PrecompileError => "PrecompileError",
+            Self::NonceOverflow => "NonceOverflow",
+            Self::CreateContractSizeLimit => "CreateContractSizeLimit",
+            Self::CreateContractStartingWithEF => "CreateContractStartingWithEF",
+            Self::CreateInitCodeSizeLimit => "CreateInitCodeSizeLimit",
+            Self::FatalExternalError => "FatalExternalError",
+            Self::ReturnContractInNotInitEOF => "ReturnContractInNotInitEOF",
+            Self::EOFOpcodeDisabledInLegacy => "EOFOpcodeDisabledInLegacy",
+            Self::EOFFunctionStackOverflow => "EOFFunctionStackOverflow",
+            Self::EofAuxDataOverflow => "EofAuxDataOverflow",
+            Self::EofAuxDataTooSmall => "EofAuxDataTooSmall",
+            Self::InvalidEXTCALLTarget => "InvalidEXTCALLTarget",
+            Self::OpcodeBudgetExceeded => "OpcodeBudgetExceeded",
+            Self::LogLimitExceeded => "LogLimitExceeded",
+            Self::DeadlineExceeded => "DeadlineExceeded",
+        }
+    }
+}
+
+impl core::fmt::Display for InstructionResult {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Internal result that are not ex
@@ -311,6 +385,9 @@ impl<EvmWiringT: EvmWiring> From<InstructionResult> for SuccessOrHalt<EvmWiringT
             InstructionResult::InvalidOperandOOG => {
                 Self::Halt(HaltReason::OutOfGas(OutOfGasError::InvalidOperand).into())
             }
+            InstructionResult::ReentrancySentryOOG => {
+                Self::Halt(HaltReason::OutOfGas(OutOfGasError::ReentrancySentry).into())
+            }
             InstructionResult::OpcodeNotFound | InstructionResult::ReturnContractInNotInitEOF => {
                 Self::Halt(HaltReason::OpcodeNotFound.into())
             }
@@ -330,10 +407,12 @@ impl<EvmWiringT: EvmWiring> From<InstructionResult> for SuccessOrHalt<EvmWiringT
             InstructionResult::OverflowPayment => Self::Halt(HaltReason::OverflowPayment.into()), // Check for first call is done separately.
             InstructionResult::PrecompileError => Self::Halt(HaltReason::PrecompileError.into()),
             InstructionResult::NonceOverflow => Self::Halt(HaltReason::NonceOverflow.into()),
-            InstructionResult::CreateContractSizeLimit
-            | InstructionResult::CreateContractStartingWithEF => {
+            InstructionResult::CreateContractSizeLimit => {
                 Self::Halt(HaltReason::CreateContractSizeLimit.into())
             }
+            InstructionResult::CreateContractStartingWithEF => {
+                Self::Halt(HaltReason::CreateContractStartingWithEF.into())
+            }
             InstructionResult::CreateInitCodeSizeLimit => {
                 Self::Halt(HaltReason::CreateInitCodeSizeLimit.into())
             }
@@ -356,6 +435,11 @@ impl<EvmWiringT: EvmWiring> From<InstructionResult> for SuccessOrHalt<EvmWiringT
             InstructionResult::InvalidEXTCALLTarget => {
                 Self::Halt(HaltReason::InvalidEXTCALLTarget.into())
             }
+            InstructionResult::OpcodeBudgetExceeded => {
+                Self::Halt(HaltReason::OpcodeBudgetExceeded.into())
+            }
+            InstructionResult::LogLimitExceeded => Self::Halt(HaltReason::LogLimitExceeded.into()),
+            InstructionResult::DeadlineExceeded => Self::Halt(HaltReason::DeadlineExceeded.into()),
             InstructionResult::InvalidExtDelegateCallTarget => {
                 Self::Internal(InternalResult::InvalidExtDelegateCallTarget)
             }
@@ -366,6 +450,7 @@ impl<EvmWiringT: EvmWiring> From<InstructionResult> for SuccessOrHalt<EvmWiringT
 #[cfg(test)]
 mod tests {
     use crate::InstructionResult;
+    use std::{format, string::ToString};
 
     #[test]
     fn all_results_are_covered() {
@@ -410,6 +495,7 @@ mod tests {
             InstructionResult::MemoryLimitOOG,
             InstructionResult::PrecompileOOG,
             InstructionResult::InvalidOperandOOG,
+            InstructionResult::ReentrancySentryOOG,
             InstructionResult::OpcodeNotFound,
             InstructionResult::CallNotAllowedInsideStatic,
             InstructionResult::StateChangeDuringStaticCall,
@@ -435,4 +521,65 @@ mod tests {
             assert!(result.is_error());
         }
     }
+
+    #[test]
+    fn display_strings() {
+        assert_eq!(InstructionResult::OutOfGas.to_string(), "OutOfGas");
+        assert_eq!(
+            InstructionResult::StackUnderflow.to_string(),
+            "StackUnderflow"
+        );
+        assert_eq!(InstructionResult::InvalidJump.to_string(), "InvalidJump");
+        assert_eq!(InstructionResult::Revert.to_string(), "Revert");
+
+        let all = [
+            InstructionResult::Continue,
+            InstructionResult::Stop,
+            InstructionResult::Return,
+            InstructionResult::SelfDestruct,
+            InstructionResult::ReturnContract,
+            InstructionResult::Revert,
+            InstructionResult::CallTooDeep,
+            InstructionResult::OutOfFunds,
+            InstructionResult::CreateInitCodeStartingEF00,
+            InstructionResult::InvalidEOFInitCode,
+            InstructionResult::InvalidExtDelegateCallTarget,
+            InstructionResult::CallOrCreate,
+            InstructionResult::OutOfGas,
+            InstructionResult::MemoryOOG,
+            InstructionResult::MemoryLimitOOG,
+            InstructionResult::PrecompileOOG,
+            InstructionResult::InvalidOperandOOG,
+            InstructionResult::ReentrancySentryOOG,
+            InstructionResult::OpcodeNotFound,
+            InstructionResult::CallNotAllowedInsideStatic,
+            InstructionResult::StateChangeDuringStaticCall,
+            InstructionResult::InvalidFEOpcode,
+            InstructionResult::InvalidJump,
+            InstructionResult::NotActivated,
+            InstructionResult::StackUnderflow,
+            InstructionResult::StackOverflow,
+            InstructionResult::OutOfOffset,
+            InstructionResult::CreateCollision,
+            InstructionResult::OverflowPayment,
+            InstructionResult::PrecompileError,
+            InstructionResult::NonceOverflow,
+            InstructionResult::CreateContractSizeLimit,
+            InstructionResult::CreateContractStartingWithEF,
+            InstructionResult::CreateInitCodeSizeLimit,
+            InstructionResult::FatalExternalError,
+            InstructionResult::ReturnContractInNotInitEOF,
+            InstructionResult::EOFOpcodeDisabledInLegacy,
+            InstructionResult::EOFFunctionStackOverflow,
+            InstructionResult::EofAuxDataOverflow,
+            InstructionResult::EofAuxDataTooSmall,
+            InstructionResult::InvalidEXTCALLTarget,
+            InstructionResult::OpcodeBudgetExceeded,
+            InstructionResult::LogLimitExceeded,
+            InstructionResult::DeadlineExceeded,
+        ];
+        for result in all {
+            assert_eq!(result.as_str(), format!("{result:?}"));
+        }
+    }
 }
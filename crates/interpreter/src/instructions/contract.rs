@@ -9,7 +9,7 @@ use crate::{
         eof::EofHeader, keccak256, Address, BerlinSpec, Bytes, Eof, Spec, SpecId::*, B256, U256,
     },
     CallInputs, CallScheme, CallValue, CreateInputs, CreateScheme, EOFCreateInputs, Host,
-    InstructionResult, InterpreterAction, InterpreterResult, MAX_INITCODE_SIZE,
+    InstructionResult, InterpreterAction, InterpreterResult,
 };
 use core::cmp::max;
 use std::boxed::Box;
@@ -171,7 +171,12 @@ pub fn extcall_gas_calc<H: Host + ?Sized>(
         return None;
     };
     // account_load.is_empty will be accounted if there is transfer value.
-    let call_cost = gas::call_cost(BerlinSpec::SPEC_ID, transfers_value, account_load);
+    let call_cost = gas::call_cost_with_costs(
+        BerlinSpec::SPEC_ID,
+        transfers_value,
+        account_load,
+        &host.env().cfg.access_gas_costs,
+    );
     gas!(interpreter, call_cost, None);
 
     // 7. Calculate the gas available to callee as caller’s
@@ -333,7 +338,7 @@ pub fn create<const IS_CREATE2: bool, H: Host + ?Sized, SPEC: Spec>(
 
     // EIP-1014: Skinny CREATE2
     if IS_CREATE2 {
-        check!(interpreter, PETERSBURG);
+        check!(interpreter, CONSTANTINOPLE);
     }
 
     pop!(interpreter, value, code_offset, len);
@@ -343,14 +348,8 @@ pub fn create<const IS_CREATE2: bool, H: Host + ?Sized, SPEC: Spec>(
     if len != 0 {
         // EIP-3860: Limit and meter initcode
         if SPEC::enabled(SHANGHAI) {
-            // Limit is set as double of max contract bytecode size
-            let max_initcode_size = host
-                .env()
-                .cfg
-                .limit_contract_code_size
-                .map(|limit| limit.saturating_mul(2))
-                .unwrap_or(MAX_INITCODE_SIZE);
-            if len > max_initcode_size {
+            // Limit is set as double of max contract bytecode size by default
+            if len > host.env().cfg.max_initcode_size() {
                 interpreter.instruction_result = InstructionResult::CreateInitCodeSizeLimit;
                 return;
             }
@@ -416,9 +415,13 @@ pub fn call<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
-    let Some(mut gas_limit) =
-        calc_call_gas::<SPEC>(interpreter, account_load, has_transfer, local_gas_limit)
-    else {
+    let Some(mut gas_limit) = calc_call_gas::<H, SPEC>(
+        interpreter,
+        host,
+        account_load,
+        has_transfer,
+        local_gas_limit,
+    ) else {
         return;
     };
 
@@ -465,7 +468,7 @@ pub fn call_code<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, ho
     // set is_empty to false as we are not creating this account.
     load.is_empty = false;
     let Some(mut gas_limit) =
-        calc_call_gas::<SPEC>(interpreter, load, !value.is_zero(), local_gas_limit)
+        calc_call_gas::<H, SPEC>(interpreter, host, load, !value.is_zero(), local_gas_limit)
     else {
         return;
     };
@@ -512,7 +515,8 @@ pub fn delegate_call<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter
     };
     // set is_empty to false as we are not creating this account.
     load.is_empty = false;
-    let Some(gas_limit) = calc_call_gas::<SPEC>(interpreter, load, false, local_gas_limit) else {
+    let Some(gas_limit) = calc_call_gas::<H, SPEC>(interpreter, host, load, false, local_gas_limit)
+    else {
         return;
     };
 
@@ -553,7 +557,8 @@ pub fn static_call<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
     };
     // set is_empty to false as we are not creating this account.
     load.is_empty = false;
-    let Some(gas_limit) = calc_call_gas::<SPEC>(interpreter, load, false, local_gas_limit) else {
+    let Some(gas_limit) = calc_call_gas::<H, SPEC>(interpreter, host, load, false, local_gas_limit)
+    else {
         return;
     };
     gas!(interpreter, gas_limit);
@@ -575,3 +580,83 @@ pub fn static_call<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
     };
     interpreter.instruction_result = InstructionResult::CallOrCreate;
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        opcode::{make_instruction_table, CREATE, CREATE2},
+        primitives::{
+            Bytecode, ByzantiumSpec, CreateScheme, DefaultEthereumWiring, LondonSpec,
+            PetersburgSpec, ShanghaiSpec,
+        },
+        DummyHost, Gas, InterpreterAction, MAX_INITCODE_SIZE,
+    };
+
+    fn create_with_initcode_len<SPEC: Spec>(
+        opcode: u8,
+        len: usize,
+        limit_initcode_size: Option<usize>,
+    ) -> Interpreter {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, SPEC>();
+        let mut host = DummyHost::default();
+        host.env.cfg.limit_initcode_size = limit_initcode_size;
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw(Bytes::from([opcode])));
+        interp.gas = Gas::new(10_000_000);
+        // CREATE pops value, offset, len and CREATE2 the salt after them.
+        if opcode == CREATE2 {
+            interp.stack.push(U256::ZERO).unwrap();
+        }
+        interp.stack.push(U256::from(len)).unwrap();
+        interp.stack.push(U256::ZERO).unwrap();
+        interp.stack.push(U256::ZERO).unwrap();
+
+        interp.step(&table, &mut host);
+        interp
+    }
+
+    #[test]
+    fn create_initcode_size_limit_is_not_enforced_before_shanghai() {
+        let interp = create_with_initcode_len::<LondonSpec>(CREATE, 50_000, None);
+        assert_eq!(interp.instruction_result, InstructionResult::CallOrCreate);
+    }
+
+    #[test]
+    fn create_initcode_size_limit_is_enforced_since_shanghai() {
+        let interp = create_with_initcode_len::<ShanghaiSpec>(CREATE, 50_000, None);
+        assert_eq!(
+            interp.instruction_result,
+            InstructionResult::CreateInitCodeSizeLimit
+        );
+
+        let interp = create_with_initcode_len::<ShanghaiSpec>(CREATE, MAX_INITCODE_SIZE, None);
+        assert_eq!(interp.instruction_result, InstructionResult::CallOrCreate);
+    }
+
+    #[test]
+    fn create_initcode_size_limit_is_configurable() {
+        let interp = create_with_initcode_len::<ShanghaiSpec>(CREATE, 50_000, Some(100_000));
+        assert_eq!(interp.instruction_result, InstructionResult::CallOrCreate);
+
+        let interp = create_with_initcode_len::<ShanghaiSpec>(CREATE, 50_000, Some(1_000));
+        assert_eq!(
+            interp.instruction_result,
+            InstructionResult::CreateInitCodeSizeLimit
+        );
+    }
+
+    #[test]
+    fn create2_is_activated_in_constantinople() {
+        let interp = create_with_initcode_len::<ByzantiumSpec>(CREATE2, 3, None);
+        assert_eq!(interp.instruction_result, InstructionResult::NotActivated);
+
+        // Petersburg is Constantinople without EIP-1283.
+        let interp = create_with_initcode_len::<PetersburgSpec>(CREATE2, 3, None);
+        assert_eq!(interp.instruction_result, InstructionResult::CallOrCreate);
+        let InterpreterAction::Create { inputs } = interp.next_action else {
+            panic!("CREATE2 did not return a create action");
+        };
+        assert_eq!(inputs.scheme, CreateScheme::Create2 { salt: U256::ZERO });
+        assert_eq!(inputs.init_code, Bytes::from([0; 3]));
+    }
+}
@@ -1,10 +1,10 @@
 use crate::{
     gas,
     interpreter::Interpreter,
-    primitives::{Bytes, Spec, SpecId::*, U256},
-    AccountLoad,
+    primitives::{Bytes, Spec, U256},
+    AccountLoad, Gas, Host,
 };
-use core::{cmp::min, ops::Range};
+use core::ops::Range;
 
 #[inline]
 pub fn get_memory_input_and_out_ranges(
@@ -43,25 +43,25 @@ pub fn resize_memory(
 }
 
 #[inline]
-pub fn calc_call_gas<SPEC: Spec>(
+pub fn calc_call_gas<H: Host + ?Sized, SPEC: Spec>(
     interpreter: &mut Interpreter,
+    host: &H,
     account_load: AccountLoad,
     has_transfer: bool,
     local_gas_limit: u64,
 ) -> Option<u64> {
-    let call_cost = gas::call_cost(SPEC::SPEC_ID, has_transfer, account_load);
+    let call_cost = gas::call_cost_with_costs(
+        SPEC::SPEC_ID,
+        has_transfer,
+        account_load,
+        &host.env().cfg.access_gas_costs,
+    );
     gas!(interpreter, call_cost, None);
 
     // EIP-150: Gas cost changes for IO-heavy operations
-    let gas_limit = if SPEC::enabled(TANGERINE) {
-        // take l64 part of gas_limit
-        min(
-            interpreter.gas().remaining_63_of_64_parts(),
-            local_gas_limit,
-        )
-    } else {
-        local_gas_limit
-    };
-
-    Some(gas_limit)
+    Some(Gas::call_gas_limit(
+        local_gas_limit,
+        interpreter.gas().remaining(),
+        SPEC::SPEC_ID,
+    ))
 }
@@ -100,7 +100,9 @@ pub fn callf<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
 
     // Check max stack height for target code section.
     // safe to subtract as max_stack_height is always more than inputs.
-    if interpreter.stack.len() + (types.max_stack_size - types.inputs as u16) as usize > 1024 {
+    if interpreter.stack.len() + (types.max_stack_size - types.inputs as u16) as usize
+        > interpreter.stack.limit()
+    {
         interpreter.instruction_result = InstructionResult::StackOverflow;
         return;
     }
@@ -138,7 +140,9 @@ pub fn jumpf<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
 
     // Check max stack height for target code section.
     // safe to subtract as max_stack_height is always more than inputs.
-    if interpreter.stack.len() + (types.max_stack_size - types.inputs as u16) as usize > 1024 {
+    if interpreter.stack.len() + (types.max_stack_size - types.inputs as u16) as usize
+        > interpreter.stack.limit()
+    {
         interpreter.instruction_result = InstructionResult::StackOverflow;
         return;
     }
@@ -1,5 +1,5 @@
 use crate::{
-    gas::{self, warm_cold_cost, warm_cold_cost_with_delegation},
+    gas::{self, warm_cold_cost_with_costs, warm_cold_cost_with_delegation_with_costs},
     interpreter::Interpreter,
     primitives::{Bytes, Log, LogData, Spec, SpecId::*, B256, U256},
     Host, InstructionResult,
@@ -16,7 +16,7 @@ pub fn balance<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host
     gas!(
         interpreter,
         if SPEC::enabled(BERLIN) {
-            warm_cold_cost(balance.is_cold)
+            warm_cold_cost_with_costs(balance.is_cold, &host.env().cfg.access_gas_costs)
         } else if SPEC::enabled(ISTANBUL) {
             // EIP-1884: Repricing for trie-size-dependent opcodes
             700
@@ -48,7 +48,10 @@ pub fn extcodesize<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
     };
     let (code, load) = code.into_components();
     if SPEC::enabled(BERLIN) {
-        gas!(interpreter, warm_cold_cost_with_delegation(load));
+        gas!(
+            interpreter,
+            warm_cold_cost_with_delegation_with_costs(load, &host.env().cfg.access_gas_costs)
+        );
     } else if SPEC::enabled(TANGERINE) {
         gas!(interpreter, 700);
     } else {
@@ -68,7 +71,10 @@ pub fn extcodehash<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
     };
     let (code_hash, load) = code_hash.into_components();
     if SPEC::enabled(BERLIN) {
-        gas!(interpreter, warm_cold_cost_with_delegation(load))
+        gas!(
+            interpreter,
+            warm_cold_cost_with_delegation_with_costs(load, &host.env().cfg.access_gas_costs)
+        )
     } else if SPEC::enabled(ISTANBUL) {
         gas!(interpreter, 700);
     } else {
@@ -90,7 +96,12 @@ pub fn extcodecopy<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
     let (code, load) = code.into_components();
     gas_or_fail!(
         interpreter,
-        gas::extcodecopy_cost(SPEC::SPEC_ID, len as u64, load)
+        gas::extcodecopy_cost_with_costs(
+            SPEC::SPEC_ID,
+            len as u64,
+            load,
+            &host.env().cfg.access_gas_costs
+        )
     );
     if len == 0 {
         return;
@@ -123,7 +134,14 @@ pub fn sload<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host:
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
-    gas!(interpreter, gas::sload_cost(SPEC::SPEC_ID, value.is_cold));
+    gas!(
+        interpreter,
+        gas::sload_cost_with_costs(
+            SPEC::SPEC_ID,
+            value.is_cold,
+            &host.env().cfg.access_gas_costs
+        )
+    );
     *index = value.data;
 }
 
@@ -135,18 +153,25 @@ pub fn sstore<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host:
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
-    gas_or_fail!(interpreter, {
-        let remaining_gas = interpreter.gas.remaining();
-        gas::sstore_cost(
+    let Some(gas_cost) = gas::sstore_cost_with_costs(
+        SPEC::SPEC_ID,
+        &state_load.data,
+        interpreter.gas.remaining(),
+        state_load.is_cold,
+        &host.env().cfg.access_gas_costs,
+    ) else {
+        // EIP-2200: fails if gas left is at or below the call stipend.
+        interpreter.instruction_result = InstructionResult::ReentrancySentryOOG;
+        return;
+    };
+    gas!(interpreter, gas_cost);
+    refund!(
+        interpreter,
+        gas::sstore_refund_with_costs(
             SPEC::SPEC_ID,
             &state_load.data,
-            remaining_gas,
-            state_load.is_cold,
+            &host.env().cfg.access_gas_costs
         )
-    });
-    refund!(
-        interpreter,
-        gas::sstore_refund(SPEC::SPEC_ID, &state_load.data)
     );
 }
 
@@ -203,7 +228,9 @@ pub fn log<const N: usize, H: Host + ?Sized>(interpreter: &mut Interpreter, host
         data: LogData::new(topics, data).expect("LogData should have <=4 topics"),
     };
 
-    host.log(log);
+    if !host.log(log) {
+        interpreter.instruction_result = InstructionResult::LogLimitExceeded;
+    }
 }
 
 pub fn selfdestruct<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
@@ -219,7 +246,138 @@ pub fn selfdestruct<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
     if !SPEC::enabled(LONDON) && !res.previously_destroyed {
         refund!(interpreter, gas::SELFDESTRUCT)
     }
-    gas!(interpreter, gas::selfdestruct_cost(SPEC::SPEC_ID, res));
+    gas!(
+        interpreter,
+        gas::selfdestruct_cost_with_costs(SPEC::SPEC_ID, res, &host.env().cfg.access_gas_costs)
+    );
 
     interpreter.instruction_result = InstructionResult::SelfDestruct;
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        opcode::{make_instruction_table, EXTCODESIZE, LOG0, PUSH1, SLOAD, SSTORE, STOP, TSTORE},
+        primitives::{
+            BerlinSpec, Bytecode, ByzantiumSpec, CancunSpec, DefaultEthereumWiring, IstanbulSpec,
+            PetersburgSpec,
+        },
+        DummyHost, Gas,
+    };
+
+    /// Runs `code` with `gas_limit` until it halts.
+    fn run<SPEC: Spec>(
+        host: &mut DummyHost<DefaultEthereumWiring>,
+        code: &[u8],
+        gas_limit: u64,
+        is_static: bool,
+    ) -> Interpreter {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, SPEC>();
+        let mut interp =
+            Interpreter::new_bytecode(Bytecode::LegacyRaw(Bytes::copy_from_slice(code)));
+        interp.gas = Gas::new(gas_limit);
+        interp.is_static = is_static;
+        while interp.instruction_result == InstructionResult::Continue {
+            interp.step(&table, host);
+        }
+        interp
+    }
+
+    #[test]
+    fn access_gas_costs_are_configurable() {
+        // the dummy host loads accounts warm and the first access of a slot cold.
+        let code = [PUSH1, 0, EXTCODESIZE, PUSH1, 0, SLOAD, STOP];
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+
+        let interp = run::<BerlinSpec>(&mut host, &code, 10_000, false);
+        assert_eq!(interp.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.gas.spent(), 2 * gas::VERYLOW + 100 + 2100);
+
+        host.clear();
+        host.env.cfg.access_gas_costs.warm_account_access = 50;
+        host.env.cfg.access_gas_costs.cold_sload = 1000;
+        let interp = run::<BerlinSpec>(&mut host, &code, 10_000, false);
+        assert_eq!(interp.gas.spent(), 2 * gas::VERYLOW + 50 + 1000);
+    }
+
+    #[test]
+    fn max_logs_halts_on_excess_log() {
+        let code = [PUSH1, 0, PUSH1, 0, LOG0].repeat(4);
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+
+        host.env.cfg.max_logs = Some(4);
+        let interp = run::<CancunSpec>(&mut host, &code, 10_000, false);
+        assert_eq!(interp.instruction_result, InstructionResult::Stop);
+        assert_eq!(host.log.len(), 4);
+
+        host.clear();
+        host.env.cfg.max_logs = Some(3);
+        let interp = run::<CancunSpec>(&mut host, &code, 10_000, false);
+        assert_eq!(
+            interp.instruction_result,
+            InstructionResult::LogLimitExceeded
+        );
+        assert_eq!(host.log.len(), 3);
+    }
+
+    #[test]
+    fn tstore_in_static_call_halts() {
+        let code = [PUSH1, 1, PUSH1, 0, TSTORE, STOP];
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+
+        let interp = run::<CancunSpec>(&mut host, &code, 10_000, true);
+        assert_eq!(
+            interp.instruction_result,
+            InstructionResult::StateChangeDuringStaticCall
+        );
+        assert!(host.transient_storage.is_empty());
+
+        let interp = run::<CancunSpec>(&mut host, &code, 10_000, false);
+        assert_eq!(interp.instruction_result, InstructionResult::Stop);
+        assert_eq!(host.transient_storage[&U256::ZERO], U256::from(1));
+    }
+
+    /// Checks that LOG0 faults in a static frame only.
+    fn log_faults_only_in_static_call<SPEC: Spec>() {
+        let code = [PUSH1, 0, PUSH1, 0, LOG0, STOP];
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+
+        let interp = run::<SPEC>(&mut host, &code, 10_000, true);
+        assert_eq!(
+            interp.instruction_result,
+            InstructionResult::StateChangeDuringStaticCall
+        );
+        assert!(host.log.is_empty());
+
+        let interp = run::<SPEC>(&mut host, &code, 10_000, false);
+        assert_eq!(interp.instruction_result, InstructionResult::Stop);
+        assert_eq!(host.log.len(), 1);
+    }
+
+    #[test]
+    fn log_faults_only_in_static_call_in_every_spec() {
+        // static-call semantics are the same in every spec since STATICCALL exists.
+        log_faults_only_in_static_call::<ByzantiumSpec>();
+        log_faults_only_in_static_call::<CancunSpec>();
+    }
+
+    #[test]
+    fn sstore_with_stipend_gas_left_fails_since_istanbul() {
+        // PUSH1 (3) + PUSH1 (3), then SSTORE of the unchanged zero slot.
+        let code = [PUSH1, 0, PUSH1, 0, SSTORE, STOP];
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+
+        let interp = run::<IstanbulSpec>(&mut host, &code, 6 + 2300, false);
+        assert_eq!(
+            interp.instruction_result,
+            InstructionResult::ReentrancySentryOOG
+        );
+        // the no-op SSTORE costs 800.
+        let interp = run::<IstanbulSpec>(&mut host, &code, 6 + 2301, false);
+        assert_eq!(interp.instruction_result, InstructionResult::Stop);
+        // before Istanbul there is no such check, the SSTORE costs 5000.
+        let interp = run::<PetersburgSpec>(&mut host, &code, 6 + 2300, false);
+        assert_eq!(interp.instruction_result, InstructionResult::OutOfGas);
+    }
+}
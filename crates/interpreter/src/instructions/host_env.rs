@@ -28,6 +28,7 @@ pub fn block_number<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut
 
 pub fn difficulty<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
     gas!(interpreter, gas::BASE);
+    // EIP-4399: the opcode returns `prevrandao` after the merge, `difficulty` is ignored.
     if SPEC::enabled(MERGE) {
         push_b256!(interpreter, *host.env().block.prevrandao().unwrap());
     } else {
@@ -73,14 +74,119 @@ pub fn blob_hash<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, ho
 pub fn blob_basefee<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
     check!(interpreter, CANCUN);
     gas!(interpreter, gas::BASE);
-    push!(
-        interpreter,
-        U256::from(
-            host.env()
-                .block
-                .get_blob_gasprice()
-                .copied()
-                .unwrap_or_default()
-        )
-    );
+    push!(interpreter, host.blob_gasprice());
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        opcode::{make_instruction_table, BASEFEE, BLOBBASEFEE, BLOBHASH, DIFFICULTY, PUSH1},
+        primitives::{
+            BerlinSpec, BlobExcessGasAndPrice, Bytecode, Bytes, CancunSpec, DefaultEthereumWiring,
+            LondonSpec, MergeSpec, ShanghaiSpec, SpecId, B256,
+        },
+        DummyHost, Gas, InstructionResult,
+    };
+
+    fn run_difficulty<SPEC: Spec>(host: &mut DummyHost<DefaultEthereumWiring>) -> U256 {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, SPEC>();
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw(Bytes::from([DIFFICULTY])));
+        interp.gas = Gas::new(10000);
+
+        interp.step(&table, host);
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        interp.stack.pop().unwrap()
+    }
+
+    #[test]
+    fn difficulty_before_merge() {
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        host.env.block.difficulty = U256::from(1234);
+        host.env.block.prevrandao = Some(B256::with_last_byte(1));
+
+        assert_eq!(run_difficulty::<LondonSpec>(&mut host), U256::from(1234));
+    }
+
+    #[test]
+    fn prevrandao_after_merge() {
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        host.env.block.difficulty = U256::from(1234);
+        host.env.block.prevrandao = Some(B256::with_last_byte(1));
+
+        assert_eq!(run_difficulty::<MergeSpec>(&mut host), U256::from(1));
+    }
+
+    /// Executes the first `steps` instructions of `code`.
+    fn run<SPEC: Spec>(
+        host: &mut DummyHost<DefaultEthereumWiring>,
+        code: &[u8],
+        steps: usize,
+    ) -> Interpreter {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, SPEC>();
+        let mut interp =
+            Interpreter::new_bytecode(Bytecode::LegacyRaw(Bytes::copy_from_slice(code)));
+        interp.gas = Gas::new(10000);
+        for _ in 0..steps {
+            interp.step(&table, host);
+        }
+        interp
+    }
+
+    #[test]
+    fn basefee_before_london() {
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        let interp = run::<BerlinSpec>(&mut host, &[BASEFEE], 1);
+        assert_eq!(interp.instruction_result, InstructionResult::NotActivated);
+    }
+
+    #[test]
+    fn basefee_after_london() {
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        host.env.block.basefee = U256::from(7);
+
+        let mut interp = run::<LondonSpec>(&mut host, &[BASEFEE], 1);
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        assert_eq!(interp.gas.spent(), gas::BASE);
+        assert_eq!(interp.stack.pop().unwrap(), U256::from(7));
+    }
+
+    #[test]
+    fn blob_opcodes_before_cancun() {
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        let interp = run::<ShanghaiSpec>(&mut host, &[PUSH1, 0, BLOBHASH], 2);
+        assert_eq!(interp.instruction_result, InstructionResult::NotActivated);
+
+        let interp = run::<ShanghaiSpec>(&mut host, &[BLOBBASEFEE], 1);
+        assert_eq!(interp.instruction_result, InstructionResult::NotActivated);
+    }
+
+    #[test]
+    fn blob_hash_after_cancun() {
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        host.env.tx.blob_hashes = vec![B256::with_last_byte(1), B256::with_last_byte(2)];
+
+        for (index, expected) in [(0, U256::from(1)), (1, U256::from(2)), (2, U256::ZERO)] {
+            let mut interp = run::<CancunSpec>(&mut host, &[PUSH1, index, BLOBHASH], 2);
+            assert_eq!(interp.instruction_result, InstructionResult::Continue);
+            assert_eq!(interp.gas.spent(), gas::VERYLOW * 2);
+            assert_eq!(interp.stack.pop().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn blob_basefee_after_cancun() {
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        let excess = BlobExcessGasAndPrice::new(10_000_000, SpecId::CANCUN);
+        host.env.block.blob_excess_gas_and_price = Some(excess.clone());
+
+        let mut interp = run::<CancunSpec>(&mut host, &[BLOBBASEFEE], 1);
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        assert_eq!(interp.gas.spent(), gas::BASE);
+        assert!(excess.blob_gasprice > 1);
+        assert_eq!(
+            interp.stack.pop().unwrap(),
+            U256::from(excess.blob_gasprice)
+        );
+    }
 }
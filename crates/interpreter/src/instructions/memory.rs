@@ -54,3 +54,68 @@ pub fn mcopy<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, _host:
     // copy memory in place
     interpreter.shared_memory.copy(dst, src, len);
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        opcode::{make_instruction_table, MCOPY, MSTORE, PUSH1, PUSH32},
+        primitives::{Bytecode, Bytes, CancunSpec, DefaultEthereumWiring, ShanghaiSpec},
+        DummyHost, Gas, InstructionResult,
+    };
+
+    /// Stores `0x01..=0x20` at offset 0 and copies `len` bytes from `src` to `dst`.
+    fn mcopy_bytecode(dst: u8, src: u8, len: u8) -> Bytecode {
+        let mut code = vec![PUSH32];
+        code.extend(1..=32u8);
+        code.extend([PUSH1, 0, MSTORE, PUSH1, len, PUSH1, src, PUSH1, dst, MCOPY]);
+        Bytecode::LegacyRaw(Bytes::from(code))
+    }
+
+    #[test]
+    fn mcopy_not_activated_before_cancun() {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, ShanghaiSpec>();
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        let mut interp = Interpreter::new_bytecode(mcopy_bytecode(0, 0, 32));
+        interp.gas = Gas::new(10000);
+
+        for _ in 0..6 {
+            interp.step(&table, &mut host);
+        }
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::NotActivated);
+    }
+
+    #[test]
+    fn mcopy_overlapping_ranges() {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, CancunSpec>();
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+
+        // forward overlap, memory is expanded to two words.
+        let mut interp = Interpreter::new_bytecode(mcopy_bytecode(1, 0, 32));
+        interp.gas = Gas::new(10000);
+        for _ in 0..6 {
+            interp.step(&table, &mut host);
+        }
+        let remaining = interp.gas.remaining();
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        // 3 + 3 * 1 word + expansion from 1 to 2 words.
+        assert_eq!(remaining - interp.gas.remaining(), 9);
+        let mut expected = vec![1u8];
+        expected.extend(1..=32u8);
+        assert_eq!(interp.shared_memory.slice(0, 33), &expected[..]);
+
+        // backward overlap within the existing word.
+        let mut interp = Interpreter::new_bytecode(mcopy_bytecode(0, 4, 8));
+        interp.gas = Gas::new(10000);
+        for _ in 0..7 {
+            interp.step(&table, &mut host);
+        }
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        let mut expected = (5..=12u8).collect::<Vec<_>>();
+        expected.extend(9..=32u8);
+        assert_eq!(interp.shared_memory.slice(0, 32), &expected[..]);
+    }
+}
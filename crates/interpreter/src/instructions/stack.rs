@@ -89,11 +89,38 @@ mod test {
 
     use super::*;
     use crate::{
-        opcode::{make_instruction_table, DUPN, EXCHANGE, SWAPN},
-        primitives::{Bytecode, Bytes, DefaultEthereumWiring, PragueSpec},
+        opcode::{make_instruction_table, DUPN, EXCHANGE, PUSH0, SWAPN},
+        primitives::{
+            Bytecode, Bytes, DefaultEthereumWiring, LondonSpec, PragueSpec, ShanghaiSpec,
+        },
         DummyHost, Gas, InstructionResult,
     };
 
+    #[test]
+    fn push0_not_activated_before_shanghai() {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, LondonSpec>();
+        let mut host = DummyHost::default();
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw(Bytes::from([PUSH0])));
+        interp.gas = Gas::new(10000);
+
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::NotActivated);
+        assert!(interp.stack.is_empty());
+    }
+
+    #[test]
+    fn push0() {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, ShanghaiSpec>();
+        let mut host = DummyHost::default();
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw(Bytes::from([PUSH0])));
+        interp.gas = Gas::new(10000);
+
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        assert_eq!(interp.stack.pop(), Ok(U256::ZERO));
+        assert_eq!(interp.gas.spent(), gas::BASE);
+    }
+
     #[test]
     fn dupn() {
         let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, PragueSpec>();
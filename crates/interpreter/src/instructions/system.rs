@@ -1,20 +1,23 @@
 use crate::{
     gas,
-    primitives::{Spec, B256, KECCAK_EMPTY, U256},
+    primitives::{Spec, B256, U256},
     Host, InstructionResult, Interpreter,
 };
 use core::ptr;
 
-pub fn keccak256<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
+pub fn keccak256<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
     pop_top!(interpreter, offset, len_ptr);
     let len = as_usize_or_fail!(interpreter, len_ptr);
     gas_or_fail!(interpreter, gas::keccak256_cost(len as u64));
     let hash = if len == 0 {
-        KECCAK_EMPTY
+        host.env().cfg.keccak.hash(&[])
     } else {
         let from = as_usize_or_fail!(interpreter, offset);
         resize_memory!(interpreter, from, len);
-        crate::primitives::keccak256(interpreter.shared_memory.slice(from, len))
+        host.env()
+            .cfg
+            .keccak
+            .hash(interpreter.shared_memory.slice(from, len))
     };
     *len_ptr = hash.into();
 }
@@ -188,8 +191,8 @@ pub fn gas<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
 mod test {
     use super::*;
     use crate::{
-        opcode::{make_instruction_table, RETURNDATACOPY, RETURNDATALOAD},
-        primitives::{bytes, Bytecode, DefaultEthereumWiring, PragueSpec},
+        opcode::{make_instruction_table, RETURNDATACOPY, RETURNDATALOAD, RETURNDATASIZE},
+        primitives::{bytes, Bytecode, ByzantiumSpec, DefaultEthereumWiring, PragueSpec},
         DummyHost, Gas, InstructionResult,
     };
 
@@ -338,4 +341,75 @@ mod test {
         assert_eq!(interp.instruction_result, InstructionResult::Continue);
         assert_eq!(&interp.shared_memory.slice(0, 32), &[0u8; 32]);
     }
+
+    #[test]
+    fn legacy_returndatacopy_faults_past_the_buffer() {
+        let table = make_instruction_table::<_, ByzantiumSpec>();
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw(
+            [RETURNDATASIZE, RETURNDATACOPY, RETURNDATACOPY].into(),
+        ));
+        interp.gas = Gas::new(10000);
+        interp.return_data_buffer = bytes!("0102030405060708");
+
+        interp.step(&table, &mut host);
+        assert_eq!(interp.stack.pop().unwrap(), U256::from(8));
+
+        // RETURNDATACOPY(0, 4, 4) reads the last four bytes.
+        interp.stack.push(U256::from(4)).unwrap();
+        interp.stack.push(U256::from(4)).unwrap();
+        interp.stack.push(U256::from(0)).unwrap();
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        assert_eq!(interp.shared_memory.slice(0, 4), &[5, 6, 7, 8]);
+
+        // RETURNDATACOPY(0, 5, 4) reads one byte past the end.
+        interp.stack.push(U256::from(4)).unwrap();
+        interp.stack.push(U256::from(5)).unwrap();
+        interp.stack.push(U256::from(0)).unwrap();
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::OutOfOffset);
+    }
+
+    #[test]
+    fn keccak256_uses_the_configured_backend() {
+        use crate::{
+            opcode::{KECCAK256, POP, PUSH1},
+            primitives::{keccak256, EnvKeccak, KeccakBackend},
+        };
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        #[derive(Debug, Default)]
+        struct CountingKeccak(AtomicUsize);
+
+        impl KeccakBackend for CountingKeccak {
+            fn keccak256(&self, input: &[u8]) -> B256 {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                keccak256(input)
+            }
+        }
+
+        let table = make_instruction_table::<_, PragueSpec>();
+        let backend = Arc::new(CountingKeccak::default());
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        host.env.cfg.keccak = EnvKeccak::Custom(backend.clone());
+
+        // the zero-length hash goes through the backend as well.
+        let mut code = [PUSH1, 32, PUSH1, 0, KECCAK256, POP].repeat(3);
+        code.extend_from_slice(&[PUSH1, 0, PUSH1, 0, KECCAK256]);
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw(code.into()));
+        interp.gas = Gas::new(10000);
+        while interp.instruction_result == InstructionResult::Continue {
+            interp.step(&table, &mut host);
+        }
+
+        assert_eq!(interp.instruction_result, InstructionResult::Stop);
+        assert_eq!(
+            interp.stack.pop().unwrap(),
+            U256::from_be_bytes(keccak256([]).0)
+        );
+        assert_eq!(backend.0.load(Ordering::Relaxed), 4);
+    }
 }
@@ -7,7 +7,7 @@ mod stack;
 
 pub use contract::Contract;
 pub use shared_memory::{num_words, SharedMemory, EMPTY_SHARED_MEMORY};
-pub use stack::{Stack, STACK_LIMIT};
+pub use stack::{Stack, MAX_STACK_LIMIT, STACK_LIMIT};
 
 use crate::{
     gas, primitives::Bytes, push, push_b256, return_ok, return_revert, CallOutcome, CreateOutcome,
@@ -71,6 +71,16 @@ impl Default for Interpreter {
 impl Interpreter {
     /// Create new interpreter
     pub fn new(contract: Contract, gas_limit: u64, is_static: bool) -> Self {
+        Self::new_with_stack(contract, gas_limit, is_static, Stack::new())
+    }
+
+    /// Create new interpreter that uses `stack`, e.g. a [`Stack::with_limit`].
+    pub fn new_with_stack(
+        contract: Contract,
+        gas_limit: u64,
+        is_static: bool,
+        stack: Stack,
+    ) -> Self {
         if !contract.bytecode.is_execution_ready() {
             panic!("Contract is not execution ready {:?}", contract.bytecode);
         }
@@ -88,7 +98,7 @@ impl Interpreter {
             is_eof_init: false,
             return_data_buffer: Bytes::new(),
             shared_memory: EMPTY_SHARED_MEMORY,
-            stack: Stack::new(),
+            stack,
             next_action: InterpreterAction::None,
         }
     }
@@ -176,8 +186,7 @@ impl Interpreter {
             return_ok!() => {
                 let address = create_outcome.address;
                 push_b256!(self, address.unwrap_or_default().into_word());
-                self.gas.erase_cost(create_outcome.gas().remaining());
-                self.gas.record_refund(create_outcome.gas().refunded());
+                self.gas.merge_child(create_outcome.gas());
             }
             return_revert!() => {
                 push!(self, U256::ZERO);
@@ -210,8 +219,7 @@ impl Interpreter {
                     self,
                     create_outcome.address.expect("EOF Address").into_word()
                 );
-                self.gas.erase_cost(create_outcome.gas().remaining());
-                self.gas.record_refund(create_outcome.gas().refunded());
+                self.gas.merge_child(create_outcome.gas());
             }
             return_revert!() => {
                 push!(self, U256::ZERO);
@@ -265,8 +273,7 @@ impl Interpreter {
         match out_ins_result {
             return_ok!() => {
                 // return unspend gas.
-                self.gas.erase_cost(out_gas.remaining());
-                self.gas.record_refund(out_gas.refunded());
+                self.gas.merge_child(&out_gas);
                 shared_memory.set(out_offset, &self.return_data_buffer[..target_len]);
                 push!(
                     self,
@@ -380,6 +387,10 @@ impl Interpreter {
     {
         self.next_action = InterpreterAction::None;
         self.shared_memory = shared_memory;
+        #[cfg(feature = "std")]
+        if let Some(deadline) = host.env().cfg.deadline {
+            self.run_with_deadline(deadline, instruction_table, host);
+        }
         // main loop
         while self.instruction_result == InstructionResult::Continue {
             self.step(instruction_table, host);
@@ -400,6 +411,35 @@ impl Interpreter {
         }
     }
 
+    /// Executes the interpreter until it returns, stops or runs past `deadline`.
+    ///
+    /// The deadline is checked on entry, so a frame resuming after a call that ran past the
+    /// deadline halts too.
+    #[cfg(feature = "std")]
+    #[cold]
+    fn run_with_deadline<FN, H: Host + ?Sized>(
+        &mut self,
+        deadline: crate::primitives::ExecutionDeadline,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+    ) where
+        FN: Fn(&mut Interpreter, &mut H),
+    {
+        let check_interval = deadline.check_interval.max(1);
+        while self.instruction_result == InstructionResult::Continue {
+            if deadline.is_exceeded() {
+                self.instruction_result = InstructionResult::DeadlineExceeded;
+                return;
+            }
+            for _ in 0..check_interval {
+                self.step(instruction_table, host);
+                if self.instruction_result != InstructionResult::Continue {
+                    return;
+                }
+            }
+        }
+    }
+
     /// Resize the memory to the new size. Returns whether the gas was enough to resize the memory.
     #[inline]
     #[must_use]
@@ -490,4 +530,28 @@ mod tests {
             >();
         let _ = interp.run(EMPTY_SHARED_MEMORY, table, host);
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn deadline_halts_infinite_loop() {
+        use crate::{
+            opcode::{JUMP, JUMPDEST, PUSH1},
+            primitives::{Bytecode, ExecutionDeadline},
+        };
+        use std::time::Duration;
+
+        let mut interp =
+            Interpreter::new_bytecode(Bytecode::LegacyRaw([JUMPDEST, PUSH1, 0, JUMP].into()));
+        interp.gas = Gas::new(u64::MAX);
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        host.env.cfg.deadline =
+            Some(ExecutionDeadline::after(Duration::from_millis(50)).with_check_interval(100));
+        let table = crate::opcode::make_instruction_table::<_, CancunSpec>();
+
+        let _ = interp.run(EMPTY_SHARED_MEMORY, &table, &mut host);
+        assert_eq!(
+            interp.instruction_result,
+            InstructionResult::DeadlineExceeded
+        );
+    }
 }
@@ -106,4 +106,68 @@ impl Contract {
             .map(|i| i.is_valid(pos))
             .unwrap_or(false)
     }
+
+    /// Returns the address whose code is executed.
+    ///
+    /// Differs from [`Contract::storage_address`] in `DELEGATECALL` and `CALLCODE` frames.
+    #[inline]
+    pub fn code_address(&self) -> Address {
+        self.bytecode_address.unwrap_or(self.target_address)
+    }
+
+    /// Returns the address whose storage and balance are used, the `ADDRESS` of the frame.
+    #[inline]
+    pub fn storage_address(&self) -> Address {
+        self.target_address
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CallScheme, CallValue};
+
+    /// Returns the contract of a frame running the code of `bytecode_address` on `target_address`.
+    fn frame(scheme: CallScheme, target_address: Address, bytecode_address: Address) -> Contract {
+        let inputs = CallInputs {
+            input: Bytes::from_static(&[0x01, 0x02, 0x03]),
+            return_memory_offset: 0..0,
+            gas_limit: 0,
+            bytecode_address,
+            target_address,
+            caller: Address::with_last_byte(1),
+            value: CallValue::Transfer(U256::ZERO),
+            scheme,
+            is_static: false,
+            is_eof: false,
+        };
+        Contract::new_with_context(inputs.input.clone(), Bytecode::new(), None, &inputs)
+    }
+
+    #[test]
+    fn frame_addresses_in_call() {
+        let a = Address::with_last_byte(0xaa);
+        let contract = frame(CallScheme::Call, a, a);
+        assert_eq!(contract.storage_address(), a);
+        assert_eq!(contract.code_address(), a);
+        assert_eq!(contract.input, Bytes::from_static(&[0x01, 0x02, 0x03]));
+    }
+
+    #[test]
+    fn frame_addresses_in_delegatecall() {
+        let a = Address::with_last_byte(0xaa);
+        let b = Address::with_last_byte(0xbb);
+        let contract = frame(CallScheme::DelegateCall, a, b);
+        assert_eq!(contract.storage_address(), a);
+        assert_eq!(contract.code_address(), b);
+    }
+
+    #[test]
+    fn code_address_defaults_to_target_address() {
+        let contract = Contract {
+            target_address: Address::with_last_byte(0xaa),
+            ..Default::default()
+        };
+        assert_eq!(contract.code_address(), contract.storage_address());
+    }
 }
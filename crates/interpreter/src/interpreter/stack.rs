@@ -8,12 +8,20 @@ use std::vec::Vec;
 /// EVM interpreter stack limit.
 pub const STACK_LIMIT: usize = 1024;
 
-/// EVM stack with [STACK_LIMIT] capacity of words.
+/// Largest limit of a [`Stack`] created with [`Stack::with_limit`].
+///
+/// The stack allocates its full capacity up front, once per call frame.
+pub const MAX_STACK_LIMIT: usize = 16 * STACK_LIMIT;
+
+/// EVM stack with, by default, [STACK_LIMIT] capacity of words.
 #[derive(Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Stack {
     /// The underlying data of the stack.
     data: Vec<U256>,
+    /// Maximum number of words, [STACK_LIMIT] unless created with [`Stack::with_limit`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    limit: usize,
 }
 
 impl fmt::Display for Stack {
@@ -40,12 +48,28 @@ impl Stack {
     /// Instantiate a new stack with the [default stack limit][STACK_LIMIT].
     #[inline]
     pub fn new() -> Self {
+        Self::with_limit(STACK_LIMIT)
+    }
+
+    /// Instantiate a new stack holding at most `limit` words, capped at [MAX_STACK_LIMIT].
+    ///
+    /// A limit other than [STACK_LIMIT] is not consensus compatible.
+    #[inline]
+    pub fn with_limit(limit: usize) -> Self {
+        let limit = limit.min(MAX_STACK_LIMIT);
         Self {
-            // SAFETY: expansion functions assume that capacity is `STACK_LIMIT`.
-            data: Vec::with_capacity(STACK_LIMIT),
+            // SAFETY: expansion functions assume that capacity is at least `limit`.
+            data: Vec::with_capacity(limit),
+            limit,
         }
     }
 
+    /// Returns the maximum number of words the stack can hold.
+    #[inline]
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
     /// Returns the length of the stack in words.
     #[inline]
     pub fn len(&self) -> usize {
@@ -201,8 +225,8 @@ impl Stack {
     #[inline]
     pub fn push(&mut self, value: U256) -> Result<(), InstructionResult> {
         // Allows the compiler to optimize out the `Vec::push` capacity check.
-        assume!(self.data.capacity() == STACK_LIMIT);
-        if self.data.len() == STACK_LIMIT {
+        assume!(self.data.capacity() >= self.limit);
+        if self.data.len() >= self.limit {
             return Err(InstructionResult::StackOverflow);
         }
         self.data.push(value);
@@ -233,7 +257,7 @@ impl Stack {
         let len = self.data.len();
         if len < n {
             Err(InstructionResult::StackUnderflow)
-        } else if len + 1 > STACK_LIMIT {
+        } else if len + 1 > self.limit {
             Err(InstructionResult::StackOverflow)
         } else {
             // SAFETY: check for out of bounds is done above and it makes this safe to do.
@@ -295,7 +319,7 @@ impl Stack {
 
         let n_words = (slice.len() + 31) / 32;
         let new_len = self.data.len() + n_words;
-        if new_len > STACK_LIMIT {
+        if new_len > self.limit {
             return Err(InstructionResult::StackOverflow);
         }
 
@@ -380,7 +404,10 @@ impl<'de> serde::Deserialize<'de> for Stack {
             )));
         }
         data.reserve(STACK_LIMIT - data.len());
-        Ok(Self { data })
+        Ok(Self {
+            data,
+            limit: STACK_LIMIT,
+        })
     }
 }
 
@@ -399,6 +426,14 @@ mod tests {
         f(&mut stack);
     }
 
+    #[test]
+    fn with_limit_is_capped() {
+        assert_eq!(Stack::with_limit(4).limit(), 4);
+        let stack = Stack::with_limit(usize::MAX);
+        assert_eq!(stack.limit(), MAX_STACK_LIMIT);
+        assert_eq!(stack.data.capacity(), MAX_STACK_LIMIT);
+    }
+
     #[test]
     fn push_slices() {
         // no-op
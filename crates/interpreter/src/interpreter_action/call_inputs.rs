@@ -213,3 +213,40 @@ impl CallValue {
         matches!(self, Self::Apparent(_))
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::{primitives::bytes, CreateInputs, CreateScheme};
+
+    #[test]
+    fn test_serde_json_roundtrip() {
+        let call = CallInputs {
+            input: bytes!("c0ffee"),
+            return_memory_offset: 32..64,
+            gas_limit: 100_000,
+            bytecode_address: Address::with_last_byte(2),
+            target_address: Address::with_last_byte(1),
+            caller: Address::with_last_byte(3),
+            value: CallValue::Apparent(U256::from(7)),
+            scheme: CallScheme::DelegateCall,
+            is_static: false,
+            is_eof: false,
+        };
+        let json = serde_json::to_string(&call).unwrap();
+        assert!(json.contains("\"0xc0ffee\""));
+        assert_eq!(serde_json::from_str::<CallInputs>(&json).unwrap(), call);
+
+        let create = CreateInputs {
+            caller: Address::with_last_byte(3),
+            scheme: CreateScheme::Create2 {
+                salt: U256::from(1),
+            },
+            value: U256::ZERO,
+            init_code: bytes!("6000"),
+            gas_limit: 100_000,
+        };
+        let json = serde_json::to_string(&create).unwrap();
+        assert_eq!(serde_json::from_str::<CreateInputs>(&json).unwrap(), create);
+    }
+}
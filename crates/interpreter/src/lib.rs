@@ -35,7 +35,7 @@ pub use host::{
 pub use instruction_result::*;
 pub use interpreter::{
     analysis, num_words, Contract, Interpreter, InterpreterResult, SharedMemory, Stack,
-    EMPTY_SHARED_MEMORY, STACK_LIMIT,
+    EMPTY_SHARED_MEMORY, MAX_STACK_LIMIT, STACK_LIMIT,
 };
 pub use interpreter_action::{
     CallInputs, CallOutcome, CallScheme, CallValue, CreateInputs, CreateOutcome, CreateScheme,
@@ -2,6 +2,9 @@
 
 pub mod eof_printer;
 
+mod diff;
+pub use diff::{opcode_diff, OpcodeChange};
+
 mod tables;
 pub use tables::{
     make_boxed_instruction_table, make_instruction_table, update_boxed_instruction,
@@ -833,4 +836,23 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn latest_spec_enables_recent_opcodes() {
+        use crate::{
+            primitives::{Bytecode, Bytes, DefaultEthereumWiring, LatestSpec},
+            DummyHost, Gas, InstructionResult, Interpreter,
+        };
+
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, LatestSpec>();
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        let code = [PUSH0, PUSH0, PUSH0, MCOPY, PUSH0, TLOAD, STOP];
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw(Bytes::from(code)));
+        interp.gas = Gas::new(10000);
+
+        for _ in 0..code.len() - 1 {
+            interp.step(&table, &mut host);
+            assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        }
+    }
 }
@@ -0,0 +1,146 @@
+//! Differences of the opcodes between two specs.
+
+use super::{make_instruction_table, OPCODE_INFO_JUMPTABLE};
+use crate::{
+    primitives::{
+        with_spec, Address, Bytecode, Bytes, DefaultEthereumWiring, Spec, SpecFn, SpecId, U256,
+    },
+    Contract, DummyHost, InstructionResult, Interpreter, InterpreterAction,
+};
+use std::{vec, vec::Vec};
+
+/// Change of an opcode between two specs, see [`opcode_diff`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OpcodeChange {
+    /// The opcode is available in the later spec only.
+    Added,
+    /// The opcode is available in the earlier spec only.
+    Removed,
+    /// The opcode is available in both specs and charges a different amount of gas.
+    Repriced {
+        /// Gas charged in the earlier spec.
+        from: u64,
+        /// Gas charged in the later spec.
+        to: u64,
+    },
+}
+
+/// Returns the opcodes that are added, removed or re-priced from spec `from` to spec `to`,
+/// sorted by opcode.
+///
+/// The opcodes are probed by executing them with the instruction table of each spec, with
+/// zero operands against a [`DummyHost`]. The compared gas is the gas charged in this
+/// setting, without gas forwarded to sub calls, so re-pricings that only affect non-zero
+/// operands or other state are not reported.
+///
+/// EOF-only opcodes are reported as available from [`SpecId::PRAGUE_EOF`] on and are not
+/// executed, so they are never reported as re-priced.
+pub fn opcode_diff(from: SpecId, to: SpecId) -> Vec<(u8, OpcodeChange)> {
+    let from_gas = with_spec(from, ProbeOpcodes);
+    let to_gas = with_spec(to, ProbeOpcodes);
+
+    (0..=u8::MAX)
+        .filter_map(|opcode| {
+            let change = match (from_gas[opcode as usize], to_gas[opcode as usize]) {
+                (None, Some(_)) => OpcodeChange::Added,
+                (Some(_), None) => OpcodeChange::Removed,
+                (Some(from), Some(to)) if from != to => OpcodeChange::Repriced { from, to },
+                _ => return None,
+            };
+            Some((opcode, change))
+        })
+        .collect()
+}
+
+/// Probes the gas charged by every opcode of a spec, `None` if not available.
+struct ProbeOpcodes;
+
+impl SpecFn for ProbeOpcodes {
+    type Output = [Option<u64>; 256];
+
+    fn call<SPEC: Spec>(self) -> Self::Output {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, SPEC>();
+        let mut gas = [None; 256];
+        for (opcode, info) in OPCODE_INFO_JUMPTABLE.iter().enumerate() {
+            let Some(info) = info else {
+                continue;
+            };
+
+            let mut code = vec![opcode as u8];
+            code.resize(1 + info.immediate_size() as usize, 0);
+            let contract = Contract::new(
+                Bytes::new(),
+                Bytecode::LegacyRaw(code.into()),
+                None,
+                Address::ZERO,
+                None,
+                Address::ZERO,
+                U256::ZERO,
+            );
+            let mut interp = Interpreter::new(contract, 1_000_000, false);
+            for _ in 0..info.inputs() {
+                interp.stack.push(U256::ZERO).unwrap();
+            }
+            let mut host = DummyHost::<DefaultEthereumWiring>::default();
+            interp.step(&table, &mut host);
+
+            gas[opcode] = match interp.instruction_result {
+                InstructionResult::NotActivated | InstructionResult::OpcodeNotFound => None,
+                InstructionResult::EOFOpcodeDisabledInLegacy => {
+                    SPEC::enabled(SpecId::PRAGUE_EOF).then_some(0)
+                }
+                _ => {
+                    let forwarded = match &interp.next_action {
+                        InterpreterAction::Call { inputs } => inputs.gas_limit,
+                        InterpreterAction::Create { inputs } => inputs.gas_limit,
+                        _ => 0,
+                    };
+                    Some(interp.gas.spent() - forwarded)
+                }
+            };
+        }
+        gas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opcode::*;
+
+    #[test]
+    fn shanghai_to_cancun() {
+        let diff = opcode_diff(SpecId::SHANGHAI, SpecId::CANCUN);
+        for opcode in [TLOAD, TSTORE, MCOPY, BLOBHASH, BLOBBASEFEE] {
+            assert!(
+                diff.contains(&(opcode, OpcodeChange::Added)),
+                "{}",
+                OpCode::name_by_op(opcode)
+            );
+        }
+        assert_eq!(diff.len(), 5);
+        assert!(opcode_diff(SpecId::CANCUN, SpecId::CANCUN).is_empty());
+    }
+
+    #[test]
+    fn istanbul_to_berlin_reprices_sload() {
+        let diff = opcode_diff(SpecId::ISTANBUL, SpecId::BERLIN);
+        assert!(diff.contains(&(
+            SLOAD,
+            OpcodeChange::Repriced {
+                from: 800,
+                to: 2100
+            }
+        )));
+        assert!(diff.contains(&(BALANCE, OpcodeChange::Repriced { from: 700, to: 100 })));
+
+        let reverse = opcode_diff(SpecId::BERLIN, SpecId::ISTANBUL);
+        assert!(reverse.contains(&(
+            SLOAD,
+            OpcodeChange::Repriced {
+                from: 2100,
+                to: 800
+            }
+        )));
+    }
+}
@@ -15,11 +15,11 @@ use revm::{
     precompile::{secp256r1, PrecompileSpecId},
     primitives::{
         db::Database, Account, Block, EVMError, EVMResult, EVMResultGeneric, EnvWiring,
-        ExecutionResult, HashMap, InvalidTransaction, ResultAndState, Transaction, U256,
+        ExecutionResult, HashMap, InvalidTransaction, ResultAndState, ResultGas, Transaction, U256,
     },
     Context, ContextPrecompiles, FrameResult,
 };
-use std::sync::Arc;
+use std::{sync::Arc, vec::Vec};
 
 pub fn optimism_handle_register<EvmWiringT>(handler: &mut EvmHandler<'_, EvmWiringT>)
 where
@@ -241,7 +241,9 @@ pub fn deduct_caller<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
 
     // If the transaction is not a deposit transaction, subtract the L1 data fee from the
     // caller's balance directly after minting the requested amount of ETH.
-    if context.evm.inner.env.tx.source_hash().is_none() {
+    if context.evm.inner.env.tx.source_hash().is_none()
+        && !context.evm.inner.env.cfg.is_fee_settlement_disabled()
+    {
         // get envelope
         let Some(enveloped_tx) = &context.evm.inner.env.tx.enveloped_tx() else {
             return Err(EVMError::Custom(
@@ -283,9 +285,10 @@ pub fn reward_beneficiary<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
         mainnet::reward_beneficiary::<EvmWiringT, SPEC>(context, gas)?;
     }
 
-    if !is_deposit {
+    if !is_deposit && !context.evm.inner.env.cfg.is_fee_settlement_disabled() {
         // If the transaction is not a deposit transaction, fees are paid out
         // to both the Base Fee Vault as well as the L1 Fee Vault.
+
         let l1_block_info = context
             .evm
             .chain
@@ -335,13 +338,24 @@ pub fn output<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
     context: &mut Context<EvmWiringT>,
     frame_result: FrameResult,
 ) -> EVMResult<EvmWiringT> {
-    let result = mainnet::output::<EvmWiringT>(context, frame_result)?;
+    let is_deposit = context.evm.inner.env.tx.source_hash().is_some();
+    // deposits pay no fees, so the coinbase only receives the value sent to it.
+    let deposit_coinbase_reward = is_deposit.then(|| {
+        let coinbase = *context.evm.inner.env.block.coinbase();
+        context.evm.inner.journaled_state.value_received(coinbase)
+    });
+    let mut result = mainnet::output::<EvmWiringT, SPEC>(context, frame_result)?;
+    if let Some(coinbase_reward) = deposit_coinbase_reward {
+        result.coinbase_reward = coinbase_reward;
+    }
+    result.minted = U256::from(context.evm.inner.env.tx.mint().cloned().unwrap_or(0));
+    // the base fee is paid to the Base Fee Vault, and deposits pay no fees.
+    result.burned = U256::ZERO;
 
     if result.result.is_halt() {
         // Post-regolith, if the transaction is a deposit transaction and it halts,
         // we bubble up to the global return handler. The mint value will be persisted
         // and the caller nonce will be incremented there.
-        let is_deposit = context.evm.inner.env.tx.source_hash().is_some();
         if is_deposit && SPEC::optimism_enabled(OptimismSpecId::REGOLITH) {
             return Err(EVMError::Transaction(
                 OptimismInvalidTransaction::HaltedDepositPostRegolith,
@@ -371,6 +385,7 @@ pub fn end<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
 
             // Increment sender nonce and account balance for the mint amount. Deposits
             // always persist the mint amount, even if the transaction fails.
+            let minted = U256::from(context.evm.inner.env().tx.mint().cloned().unwrap_or(0));
             let account = {
                 let mut acc = Account::from(
                     context
@@ -381,9 +396,7 @@ pub fn end<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
                         .unwrap_or_default(),
                 );
                 acc.info.nonce = acc.info.nonce.saturating_add(1);
-                acc.info.balance = acc.info.balance.saturating_add(U256::from(
-                    context.evm.inner.env().tx.mint().cloned().unwrap_or(0),
-                ));
+                acc.info.balance = acc.info.balance.saturating_add(minted);
                 acc.mark_touch();
                 acc
             };
@@ -405,12 +418,22 @@ pub fn end<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
                 0
             };
 
+            // the intrinsic gas is reported as such, the rest of the used gas as execution gas.
+            let is_create = context.evm.inner.env().tx.kind().is_create();
+            let intrinsic_gas =
+                mainnet::validate_initial_tx_gas::<EvmWiringT, SPEC>(context.evm.inner.env())
+                    .map_or(gas_used, |intrinsic_gas| intrinsic_gas.min(gas_used));
             Ok(ResultAndState {
                 result: ExecutionResult::Halt {
                     reason: OptimismHaltReason::FailedDeposit,
                     gas_used,
                 },
                 state,
+                gas: ResultGas::new(is_create, intrinsic_gas, gas_used, 0),
+                coinbase_reward: U256::ZERO,
+                minted,
+                burned: U256::ZERO,
+                selfdestructed: Vec::new(),
             })
         } else {
             Err(err)
@@ -424,8 +447,9 @@ mod tests {
     use crate::{BedrockSpec, L1BlockInfo, LatestSpec, OptimismEvmWiring, RegolithSpec};
     use revm::{
         db::{EmptyDB, InMemoryDB},
-        interpreter::{CallOutcome, InterpreterResult},
-        primitives::{bytes, state::AccountInfo, Address, Bytes, B256},
+        interpreter::{opcode, CallOutcome, InterpreterResult},
+        primitives::{bytes, state::AccountInfo, Address, Bytecode, Bytes, TxKind, B256},
+        Evm,
     };
     use std::boxed::Box;
 
@@ -678,6 +702,31 @@ mod tests {
         assert!(validate_env::<TestEmptyOpWiring, RegolithSpec>(&env).is_ok());
     }
 
+    #[test]
+    fn test_validate_deposit_tx_skips_mainnet_validation() {
+        // a deposit paying less than the base fee, which mainnet rejects.
+        let mut env = EnvWiring::<TestEmptyOpWiring>::default();
+        env.block.basefee = U256::from(10);
+        env.tx.base.gas_price = U256::from(1);
+        env.tx.source_hash = Some(B256::ZERO);
+        assert_eq!(
+            mainnet::validate_env::<TestEmptyOpWiring, RegolithSpec>(&env),
+            Err(EVMError::Transaction(OptimismInvalidTransaction::Base(
+                InvalidTransaction::GasPriceLessThanBasefee
+            )))
+        );
+        assert!(validate_env::<TestEmptyOpWiring, RegolithSpec>(&env).is_ok());
+
+        // the same transaction is rejected if it is not a deposit.
+        env.tx.source_hash = None;
+        assert_eq!(
+            validate_env::<TestEmptyOpWiring, RegolithSpec>(&env),
+            Err(EVMError::Transaction(OptimismInvalidTransaction::Base(
+                InvalidTransaction::GasPriceLessThanBasefee
+            )))
+        );
+    }
+
     #[test]
     fn test_validate_tx_against_state_deposit_tx() {
         // Set source hash.
@@ -687,4 +736,65 @@ mod tests {
         // Nonce and balance checks should be skipped for deposit transactions.
         assert!(validate_env::<TestEmptyOpWiring, LatestSpec>(&env).is_ok());
     }
+
+    fn transact_deposit(code: Bytecode) -> ResultAndState<OptimismHaltReason> {
+        let caller = Address::with_last_byte(0xca);
+        let contract = Address::with_last_byte(0xc0);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            caller,
+            AccountInfo {
+                nonce: 3,
+                ..Default::default()
+            },
+        );
+        db.insert_account_info(contract, AccountInfo::from_bytecode(code));
+
+        let mut evm = Evm::<TestMemOpWiring>::builder()
+            .with_db(db)
+            .with_default_ext_ctx()
+            .with_spec_id(OptimismSpecId::REGOLITH)
+            .modify_tx_env(|tx| {
+                tx.base.caller = caller;
+                tx.base.transact_to = TxKind::Call(contract);
+                tx.base.gas_limit = 100_000;
+                tx.base.nonce = 3;
+                tx.source_hash = Some(B256::ZERO);
+                tx.mint = Some(10);
+                tx.is_system_transaction = Some(false);
+            })
+            .build();
+
+        let result = evm.transact().unwrap();
+        let account = &result.state[&caller];
+        assert_eq!(account.info.nonce, 4);
+        assert_eq!(account.info.balance, U256::from(10));
+        assert_eq!(result.minted, U256::from(10));
+        assert_eq!(result.burned, U256::ZERO);
+        result
+    }
+
+    #[test]
+    fn test_reverted_deposit_keeps_nonce_and_mint() {
+        let result = transact_deposit(Bytecode::new_legacy(
+            [opcode::PUSH1, 0, opcode::PUSH1, 0, opcode::REVERT].into(),
+        ));
+        assert!(matches!(result.result, ExecutionResult::Revert { .. }));
+    }
+
+    #[test]
+    fn test_halted_deposit_keeps_nonce_and_mint() {
+        let result = transact_deposit(Bytecode::new_legacy([opcode::INVALID].into()));
+        assert_eq!(
+            result.result,
+            ExecutionResult::Halt {
+                reason: OptimismHaltReason::FailedDeposit,
+                gas_used: 100_000,
+            }
+        );
+        assert_eq!(result.gas.gas_used(), 100_000);
+        assert_eq!(result.gas.intrinsic_gas, 21_000);
+        assert_eq!(result.gas.execution_gas, 79_000);
+    }
 }
@@ -14,7 +14,11 @@ pub fn run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
     let input = &input[..];
 
     if input.len() != INPUT_LENGTH {
-        return Err(Error::Blake2WrongLength.into());
+        return Err(Error::InvalidInputLength {
+            expected: INPUT_LENGTH,
+            got: input.len(),
+        }
+        .into());
     }
 
     // rounds 4 bytes
@@ -27,10 +27,10 @@ pub(super) fn g1_add(input: &Bytes, gas_limit: u64) -> PrecompileResult {
     }
 
     if input.len() != INPUT_LENGTH {
-        return Err(PrecompileError::Other(format!(
-            "G1ADD input should be {INPUT_LENGTH} bytes, was {}",
-            input.len()
-        ))
+        return Err(PrecompileError::InvalidInputLength {
+            expected: INPUT_LENGTH,
+            got: input.len(),
+        }
         .into());
     }
 
@@ -27,10 +27,10 @@ pub(super) fn map_fp2_to_g2(input: &Bytes, gas_limit: u64) -> PrecompileResult {
     }
 
     if input.len() != PADDED_FP2_LENGTH {
-        return Err(PrecompileError::Other(format!(
-            "MAP_FP2_TO_G2 input should be {PADDED_FP2_LENGTH} bytes, was {}",
-            input.len()
-        ))
+        return Err(PrecompileError::InvalidInputLength {
+            expected: PADDED_FP2_LENGTH,
+            got: input.len(),
+        }
         .into());
     }
 
@@ -25,10 +25,10 @@ pub(super) fn map_fp_to_g1(input: &Bytes, gas_limit: u64) -> PrecompileResult {
     }
 
     if input.len() != PADDED_FP_LENGTH {
-        return Err(PrecompileError::Other(format!(
-            "MAP_FP_TO_G1 input should be {PADDED_FP_LENGTH} bytes, was {}",
-            input.len()
-        ))
+        return Err(PrecompileError::InvalidInputLength {
+            expected: PADDED_FP_LENGTH,
+            got: input.len(),
+        }
         .into());
     }
 
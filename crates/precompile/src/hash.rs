@@ -46,3 +46,21 @@ pub fn ripemd160_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
         Ok(PrecompileOutput::new(gas_used, output.to_vec().into()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::hex;
+
+    #[test]
+    fn ripemd160_output_is_left_padded_to_32_bytes() {
+        let output = ripemd160_run(&Bytes::new(), 600).unwrap();
+        assert_eq!(output.gas_used, 600);
+        assert_eq!(output.bytes.len(), 32);
+        assert_eq!(output.bytes[..12], [0u8; 12]);
+        assert_eq!(
+            output.bytes[12..],
+            hex!("9c1185a5c5e9fc54612808977ee8f548b2258d31")
+        );
+    }
+}
@@ -37,7 +37,11 @@ pub fn run(input: &Bytes, gas_limit: u64, cfg: &CfgEnv) -> PrecompileResult {
 
     // Verify input length.
     if input.len() != 192 {
-        return Err(Error::BlobInvalidInputLength.into());
+        return Err(Error::InvalidInputLength {
+            expected: 192,
+            got: input.len(),
+        }
+        .into());
     }
 
     // Verify commitment matches versioned_hash
@@ -123,4 +127,18 @@ mod tests {
         assert_eq!(output.gas_used, gas);
         assert_eq!(output.bytes[..], expected_output);
     }
+
+    #[test]
+    fn invalid_input_length() {
+        let input = Bytes::from(vec![0u8; 100]);
+        let err = run(&input, 50000, &CfgEnv::default()).unwrap_err();
+        assert_eq!(
+            err,
+            Error::InvalidInputLength {
+                expected: 192,
+                got: 100
+            }
+            .into()
+        );
+    }
 }
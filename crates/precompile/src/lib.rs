@@ -190,6 +190,16 @@ impl Precompiles {
         self.inner.keys()
     }
 
+    /// Returns the addresses of the precompiles active at the given spec, in ascending order.
+    pub fn addresses_for_spec(spec: revm_primitives::SpecId) -> impl Iterator<Item = Address> {
+        let mut addresses = Self::new(PrecompileSpecId::from_spec_id(spec))
+            .addresses()
+            .copied()
+            .collect::<Vec<_>>();
+        addresses.sort_unstable();
+        addresses.into_iter()
+    }
+
     /// Consumes the type and returns all precompile addresses.
     #[inline]
     pub fn into_addresses(self) -> impl ExactSizeIterator<Item = Address> {
@@ -309,3 +319,36 @@ pub const fn u64_to_address(x: u64) -> Address {
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, x[0], x[1], x[2], x[3], x[4], x[5], x[6], x[7],
     ])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::SpecId;
+
+    #[test]
+    #[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
+    fn addresses_for_spec() {
+        let point_evaluation = u64_to_address(0x0a);
+
+        let cancun = Precompiles::addresses_for_spec(SpecId::CANCUN).collect::<Vec<_>>();
+        assert!(cancun.contains(&point_evaluation));
+        assert!(cancun.windows(2).all(|w| w[0] < w[1]));
+
+        let berlin = Precompiles::addresses_for_spec(SpecId::BERLIN).collect::<Vec<_>>();
+        assert!(!berlin.contains(&point_evaluation));
+        assert_eq!(berlin, (1..=9).map(u64_to_address).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn latest_is_superset_of_every_spec() {
+        let latest = Precompiles::addresses_for_spec(SpecId::LATEST).collect::<HashSet<_>>();
+        for spec in (0..=u8::MAX).filter_map(SpecId::try_from_u8) {
+            assert!(Precompiles::addresses_for_spec(spec).all(|address| latest.contains(&address)));
+        }
+
+        #[cfg(feature = "blst")]
+        for precompile in bls12_381::precompiles() {
+            assert!(latest.contains(precompile.address()));
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Builder of canonical access lists.
+
+use crate::{AccessList, AccessListItem, Address, B256};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Builds an [`AccessList`] without duplicates, with addresses and storage keys sorted.
+///
+/// The built list is canonical, the same set of addresses and slots always results in the
+/// same list, independently of the order and the number of times they are added.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessListBuilder {
+    items: BTreeMap<Address, BTreeSet<B256>>,
+}
+
+impl AccessListBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `address` without storage keys.
+    pub fn add_address(&mut self, address: Address) -> &mut Self {
+        self.items.entry(address).or_default();
+        self
+    }
+
+    /// Adds the storage key `slot` of `address`, and `address` itself.
+    pub fn add_slot(&mut self, address: Address, slot: B256) -> &mut Self {
+        self.items.entry(address).or_default().insert(slot);
+        self
+    }
+
+    /// Returns the access list.
+    pub fn build(&self) -> AccessList {
+        AccessList(
+            self.items
+                .iter()
+                .map(|(address, slots)| AccessListItem {
+                    address: *address,
+                    storage_keys: slots.iter().copied().collect(),
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Extend<AccessListItem> for AccessListBuilder {
+    fn extend<T: IntoIterator<Item = AccessListItem>>(&mut self, iter: T) {
+        for item in iter {
+            self.items
+                .entry(item.address)
+                .or_default()
+                .extend(item.storage_keys);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec;
+
+    #[test]
+    fn dedups_and_sorts() {
+        let a = Address::with_last_byte(1);
+        let b = Address::with_last_byte(2);
+        let slot = B256::with_last_byte;
+
+        let mut builder = AccessListBuilder::new();
+        builder
+            .add_slot(b, slot(2))
+            .add_address(a)
+            .add_slot(b, slot(1))
+            .add_slot(b, slot(2))
+            .add_address(b);
+        builder.extend([AccessListItem {
+            address: a,
+            storage_keys: vec![slot(3), slot(3)],
+        }]);
+
+        assert_eq!(
+            builder.build(),
+            AccessList(vec![
+                AccessListItem {
+                    address: a,
+                    storage_keys: vec![slot(3)],
+                },
+                AccessListItem {
+                    address: b,
+                    storage_keys: vec![slot(1), slot(2)],
+                },
+            ])
+        );
+    }
+}
@@ -51,6 +51,32 @@ impl Bytecode {
         }
     }
 
+    /// Returns `true` if `pc` is the offset of a `JUMPDEST` that is not part of `PUSH` data.
+    ///
+    /// Analyzed legacy bytecode consults its jump table, raw legacy bytecode is scanned.
+    /// EOF and EIP-7702 bytecode have no jump destinations and always return `false`.
+    pub fn is_valid_jump_dest(&self, pc: usize) -> bool {
+        const JUMPDEST: u8 = 0x5B;
+        const PUSH1: u8 = 0x60;
+
+        match self {
+            Self::LegacyAnalyzed(analyzed) => analyzed.jump_table().is_valid(pc),
+            Self::LegacyRaw(bytes) => {
+                let mut i = 0;
+                while i < pc && i < bytes.len() {
+                    let push_size = bytes[i].wrapping_sub(PUSH1);
+                    i += if push_size < 32 {
+                        push_size as usize + 2
+                    } else {
+                        1
+                    };
+                }
+                i == pc && bytes.get(pc) == Some(&JUMPDEST)
+            }
+            Self::Eof(_) | Self::Eip7702(_) => false,
+        }
+    }
+
     /// Calculate hash of the bytecode.
     pub fn hash_slow(&self) -> B256 {
         if self.is_empty() {
@@ -248,8 +274,29 @@ impl fmt::Display for BytecodeDecodeError {
 
 #[cfg(test)]
 mod tests {
-    use super::{Bytecode, Eof};
-    use std::sync::Arc;
+    use super::{Bytecode, Eof, JumpTable, LegacyAnalyzedBytecode};
+    use crate::Bytes;
+    use std::{sync::Arc, vec::Vec};
+
+    #[test]
+    fn legacy_jump_dest() {
+        // PUSH1 0x5B, JUMPDEST, PUSH2 0x5B 0x5B, STOP, JUMPDEST
+        let code = Bytes::from_static(&[0x60, 0x5B, 0x5B, 0x61, 0x5B, 0x5B, 0x00, 0x5B]);
+        let raw = Bytecode::new_legacy(code.clone());
+        let analyzed = Bytecode::LegacyAnalyzed(LegacyAnalyzedBytecode::new(
+            code,
+            8,
+            JumpTable::from_slice(&[0b1000_0100]),
+        ));
+
+        for bytecode in [&raw, &analyzed] {
+            let valid: Vec<_> = (0..10)
+                .filter(|&pc| bytecode.is_valid_jump_dest(pc))
+                .collect();
+            assert_eq!(valid, [2, 7]);
+        }
+        assert!(!Bytecode::Eof(Arc::new(Eof::default())).is_valid_jump_dest(0));
+    }
 
     #[test]
     fn eof_arc_clone() {
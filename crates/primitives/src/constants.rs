@@ -35,6 +35,15 @@ pub const MAX_INITCODE_SIZE: usize = 2 * MAX_CODE_SIZE;
 pub const PRECOMPILE3: Address =
     Address::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3]);
 
+// === EIP-1559 constants ===
+
+/// Base fee of the first London block.
+pub const INITIAL_BASE_FEE: u64 = 1_000_000_000;
+
+/// Bounds the change of the base fee between blocks to `1 / BASE_FEE_MAX_CHANGE_DENOMINATOR`
+/// of the parent base fee.
+pub const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
+
 // === EIP-4844 constants ===
 
 /// Gas consumption of a single data blob (== blob byte size).
@@ -52,11 +61,30 @@ pub const MAX_BLOB_GAS_PER_BLOCK: u64 = MAX_BLOB_NUMBER_PER_BLOCK * GAS_PER_BLOB
 /// Target consumable blob gas for data blobs per block (for 1559-like pricing).
 pub const TARGET_BLOB_GAS_PER_BLOCK: u64 = TARGET_BLOB_NUMBER_PER_BLOCK * GAS_PER_BLOB;
 
+/// Target number of the blob per block after Prague.
+///
+/// See [EIP-7691](https://eips.ethereum.org/EIPS/eip-7691).
+pub const TARGET_BLOB_NUMBER_PER_BLOCK_PRAGUE: u64 = 6;
+
+/// Max number of blobs per block after Prague.
+///
+/// See [EIP-7691](https://eips.ethereum.org/EIPS/eip-7691).
+pub const MAX_BLOB_NUMBER_PER_BLOCK_PRAGUE: u64 = 9;
+
+/// Target consumable blob gas for data blobs per block after Prague.
+pub const TARGET_BLOB_GAS_PER_BLOCK_PRAGUE: u64 =
+    TARGET_BLOB_NUMBER_PER_BLOCK_PRAGUE * GAS_PER_BLOB;
+
 /// Minimum gas price for data blobs.
 pub const MIN_BLOB_GASPRICE: u64 = 1;
 
 /// Controls the maximum rate of change for blob gas price.
 pub const BLOB_GASPRICE_UPDATE_FRACTION: u64 = 3338477;
 
+/// Controls the maximum rate of change for blob gas price after Prague.
+///
+/// See [EIP-7691](https://eips.ethereum.org/EIPS/eip-7691).
+pub const BLOB_GASPRICE_UPDATE_FRACTION_PRAGUE: u64 = 5007716;
+
 /// First version of the blob.
 pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
@@ -1,13 +1,21 @@
 use crate::{Account, AccountInfo, Address, Bytecode, HashMap, B256, U256};
 use auto_impl::auto_impl;
 
+pub mod block_hash_provider;
+pub mod buffered;
 pub mod components;
 pub mod emptydb;
+pub mod error_context;
+pub mod fallback;
 
+pub use block_hash_provider::{BlockHashProvider, WithBlockHashProvider};
+pub use buffered::BufferedDatabaseRef;
 pub use components::{
     BlockHash, BlockHashRef, DatabaseComponentError, DatabaseComponents, State, StateRef,
 };
 pub use emptydb::{EmptyDB, EmptyDBTyped};
+pub use error_context::{DatabaseError, DbOp, WithErrorContext};
+pub use fallback::{FallbackDb, FallbackDbError};
 
 /// EVM database interface.
 #[auto_impl(&mut, Box)]
@@ -0,0 +1,147 @@
+//! Block hash resolution that is decoupled from the state [`Database`].
+
+use crate::{
+    db::{Database, DatabaseCommit, DatabaseComponentError, DatabaseRef},
+    Account, AccountInfo, Address, Bytecode, HashMap, B256, U256,
+};
+
+/// Source of block hashes for the `BLOCKHASH` opcode.
+///
+/// Integrators that keep headers in a store separate from the state can implement this
+/// and plug it in with [`WithBlockHashProvider`].
+///
+/// Every [`DatabaseRef`] is a provider through its `block_hash_ref` method.
+pub trait BlockHashProvider {
+    /// The provider error type.
+    type Error;
+
+    /// Get block hash by block number.
+    fn block_hash(&self, number: u64) -> Result<B256, Self::Error>;
+}
+
+impl<T: DatabaseRef> BlockHashProvider for T {
+    type Error = T::Error;
+
+    #[inline]
+    fn block_hash(&self, number: u64) -> Result<B256, Self::Error> {
+        self.block_hash_ref(number)
+    }
+}
+
+/// Database adapter that reads state from `db` and resolves block hashes with `provider`.
+///
+/// When used as the backing database of a `CacheDB`, uncached block hash lookups are
+/// delegated to the provider.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WithBlockHashProvider<DB, P> {
+    /// State database.
+    pub db: DB,
+    /// Block hash provider.
+    pub provider: P,
+}
+
+impl<DB, P> WithBlockHashProvider<DB, P> {
+    /// Creates a new adapter from a state database and a block hash provider.
+    pub fn new(db: DB, provider: P) -> Self {
+        Self { db, provider }
+    }
+}
+
+impl<DB: Database, P: BlockHashProvider> Database for WithBlockHashProvider<DB, P> {
+    type Error = DatabaseComponentError<DB::Error, P::Error>;
+
+    #[inline]
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.db.basic(address).map_err(Self::Error::State)
+    }
+
+    #[inline]
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.db.code_by_hash(code_hash).map_err(Self::Error::State)
+    }
+
+    #[inline]
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.db.storage(address, index).map_err(Self::Error::State)
+    }
+
+    #[inline]
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.provider
+            .block_hash(number)
+            .map_err(Self::Error::BlockHash)
+    }
+}
+
+impl<DB: DatabaseRef, P: BlockHashProvider> DatabaseRef for WithBlockHashProvider<DB, P> {
+    type Error = DatabaseComponentError<DB::Error, P::Error>;
+
+    #[inline]
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.db.basic_ref(address).map_err(Self::Error::State)
+    }
+
+    #[inline]
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.db
+            .code_by_hash_ref(code_hash)
+            .map_err(Self::Error::State)
+    }
+
+    #[inline]
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.db
+            .storage_ref(address, index)
+            .map_err(Self::Error::State)
+    }
+
+    #[inline]
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.provider
+            .block_hash(number)
+            .map_err(Self::Error::BlockHash)
+    }
+}
+
+impl<DB: DatabaseCommit, P> DatabaseCommit for WithBlockHashProvider<DB, P> {
+    #[inline]
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        self.db.commit(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::EmptyDB, keccak256};
+    use core::convert::Infallible;
+
+    struct Headers;
+
+    impl BlockHashProvider for Headers {
+        type Error = Infallible;
+
+        fn block_hash(&self, number: u64) -> Result<B256, Self::Error> {
+            Ok(B256::with_last_byte(number as u8))
+        }
+    }
+
+    #[test]
+    fn block_hash_is_served_by_provider() {
+        let mut db = WithBlockHashProvider::new(EmptyDB::new(), Headers);
+        assert_eq!(
+            Database::block_hash(&mut db, 7).unwrap(),
+            B256::with_last_byte(7)
+        );
+        assert_eq!(db.block_hash_ref(9).unwrap(), B256::with_last_byte(9));
+    }
+
+    #[test]
+    fn database_ref_is_a_provider() {
+        let number = 42u64;
+        assert_eq!(
+            BlockHashProvider::block_hash(&EmptyDB::new(), number).unwrap(),
+            keccak256(number.to_string().as_bytes())
+        );
+    }
+}
@@ -0,0 +1,160 @@
+//! [`DatabaseRef`] adapter that buffers committed writes in memory.
+
+use crate::{
+    db::{Database, DatabaseCommit, DatabaseRef},
+    Account, AccountInfo, Address, Bytecode, HashMap, B256, U256,
+};
+
+/// Wraps a [`DatabaseRef`] to provide a [`Database`] that keeps committed changes in a write
+/// buffer instead of applying them to the wrapped database.
+///
+/// Unlike `CacheDB`, reads of the wrapped database are not cached, only written accounts
+/// are served from memory. This is useful when the read side is already cached elsewhere.
+///
+/// Pending changes are extracted with [`BufferedDatabaseRef::drain_writes`].
+#[derive(Clone, Debug, Default)]
+pub struct BufferedDatabaseRef<T> {
+    /// Wrapped database.
+    pub db: T,
+    /// Written accounts with the written storage slots.
+    writes: HashMap<Address, Account>,
+    /// Code of the written accounts.
+    contracts: HashMap<B256, Bytecode>,
+}
+
+impl<T> BufferedDatabaseRef<T> {
+    /// Creates a new adapter with an empty write buffer.
+    pub fn new(db: T) -> Self {
+        Self {
+            db,
+            writes: HashMap::new(),
+            contracts: HashMap::new(),
+        }
+    }
+
+    /// Returns the pending changes.
+    pub fn writes(&self) -> &HashMap<Address, Account> {
+        &self.writes
+    }
+
+    /// Takes the pending changes, leaving the write buffer empty.
+    ///
+    /// The returned accounts can be committed to another [`DatabaseCommit`]. Accounts that
+    /// were destroyed are marked as self-destructed, accounts that were created are
+    /// marked as created and only contain the storage written since.
+    pub fn drain_writes(&mut self) -> HashMap<Address, Account> {
+        self.contracts.clear();
+        core::mem::take(&mut self.writes)
+    }
+}
+
+impl<T: DatabaseRef> Database for BufferedDatabaseRef<T> {
+    type Error = T::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        match self.writes.get(&address) {
+            Some(account) if account.is_selfdestructed() => Ok(None),
+            Some(account) => Ok(Some(account.info.clone())),
+            None => self.db.basic_ref(address),
+        }
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        match self.contracts.get(&code_hash) {
+            Some(code) => Ok(code.clone()),
+            None => self.db.code_by_hash_ref(code_hash),
+        }
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let Some(account) = self.writes.get(&address) else {
+            return self.db.storage_ref(address, index);
+        };
+        match account.storage.get(&index) {
+            Some(slot) => Ok(slot.present_value()),
+            None if account.is_created() || account.is_selfdestructed() => Ok(U256::ZERO),
+            None => self.db.storage_ref(address, index),
+        }
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.db.block_hash_ref(number)
+    }
+}
+
+impl<T> DatabaseCommit for BufferedDatabaseRef<T> {
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        for (address, mut account) in changes {
+            if !account.is_touched() {
+                continue;
+            }
+            if account.is_selfdestructed() {
+                account.info = AccountInfo::default();
+                account.storage.clear();
+                self.writes.insert(address, account);
+                continue;
+            }
+            if let Some(code) = &account.info.code {
+                if !code.is_empty() {
+                    self.contracts.insert(account.info.code_hash, code.clone());
+                }
+            }
+            match self.writes.get_mut(&address) {
+                Some(pending) if pending.is_selfdestructed() => {
+                    // the storage of the destroyed account stays cleared.
+                    account.mark_created();
+                    *pending = account;
+                }
+                Some(pending) if !account.is_created() => {
+                    pending.info = account.info;
+                    for (index, slot) in account.storage {
+                        pending
+                            .storage
+                            .entry(index)
+                            .and_modify(|pending| pending.present_value = slot.present_value)
+                            .or_insert(slot);
+                    }
+                }
+                _ => {
+                    self.writes.insert(address, account);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::EmptyDB, AccountStatus, EvmStorageSlot};
+
+    #[test]
+    fn drains_written_account() {
+        let address = Address::with_last_byte(1);
+        let mut db = BufferedDatabaseRef::new(EmptyDB::new());
+
+        let mut account = Account::from(AccountInfo::from_balance(U256::from(10)));
+        account.status = AccountStatus::Touched;
+        account.storage.insert(
+            U256::from(1),
+            EvmStorageSlot::new_changed(U256::ZERO, U256::from(2)),
+        );
+        db.commit(HashMap::from_iter([(address, account)]));
+
+        assert_eq!(db.basic(address).unwrap().unwrap().balance, U256::from(10));
+        assert_eq!(db.storage(address, U256::from(1)).unwrap(), U256::from(2));
+        assert_eq!(db.db.basic_ref(address).unwrap(), None);
+
+        let writes = db.drain_writes();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[&address].info.balance, U256::from(10));
+        assert_eq!(
+            writes[&address].storage[&U256::from(1)].present_value(),
+            U256::from(2)
+        );
+
+        // reads fall back to the wrapped database once the buffer is drained.
+        assert_eq!(db.basic(address).unwrap(), None);
+        assert_eq!(db.storage(address, U256::from(1)).unwrap(), U256::ZERO);
+    }
+}
@@ -0,0 +1,209 @@
+//! Database adapter that records which operation a database error originates from.
+
+use crate::{
+    db::{Database, DatabaseCommit, DatabaseRef},
+    Account, AccountInfo, Address, Bytecode, HashMap, B256, U256,
+};
+use core::fmt;
+
+/// Database operation, with the key it was called with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DbOp {
+    /// [`Database::basic`] of an account.
+    Basic(Address),
+    /// [`Database::code_by_hash`] of a code hash.
+    CodeByHash(B256),
+    /// [`Database::storage`] of a storage slot.
+    Storage {
+        /// Address of the account.
+        address: Address,
+        /// Index of the slot.
+        index: U256,
+    },
+    /// [`Database::block_hash`] of a block number.
+    BlockHash(u64),
+}
+
+impl fmt::Display for DbOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Basic(address) => write!(f, "basic({address})"),
+            Self::CodeByHash(code_hash) => write!(f, "code_by_hash({code_hash})"),
+            Self::Storage { address, index } => write!(f, "storage({address}, {index})"),
+            Self::BlockHash(number) => write!(f, "block_hash({number})"),
+        }
+    }
+}
+
+/// Error of a [`WithErrorContext`] database, the error of the wrapped database together
+/// with the operation that failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DatabaseError<E> {
+    /// Failed operation.
+    pub op: DbOp,
+    /// Error of the wrapped database.
+    pub error: E,
+}
+
+impl<E: fmt::Display> fmt::Display for DatabaseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} failed: {}", self.op, self.error)
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for DatabaseError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Database adapter that wraps the errors of `db` in a [`DatabaseError`].
+///
+/// Database errors surface as `EVMError::Database` without saying which lookup failed.
+/// With this adapter the error carries the [`DbOp`], which helps debugging backends that
+/// intermittently fail single lookups, like remote providers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WithErrorContext<DB>(pub DB);
+
+impl<DB: Database> Database for WithErrorContext<DB> {
+    type Error = DatabaseError<DB::Error>;
+
+    #[inline]
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.0.basic(address).map_err(|error| DatabaseError {
+            op: DbOp::Basic(address),
+            error,
+        })
+    }
+
+    #[inline]
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.0
+            .code_by_hash(code_hash)
+            .map_err(|error| DatabaseError {
+                op: DbOp::CodeByHash(code_hash),
+                error,
+            })
+    }
+
+    #[inline]
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.0
+            .storage(address, index)
+            .map_err(|error| DatabaseError {
+                op: DbOp::Storage { address, index },
+                error,
+            })
+    }
+
+    #[inline]
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.0.block_hash(number).map_err(|error| DatabaseError {
+            op: DbOp::BlockHash(number),
+            error,
+        })
+    }
+}
+
+impl<DB: DatabaseRef> DatabaseRef for WithErrorContext<DB> {
+    type Error = DatabaseError<DB::Error>;
+
+    #[inline]
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.0.basic_ref(address).map_err(|error| DatabaseError {
+            op: DbOp::Basic(address),
+            error,
+        })
+    }
+
+    #[inline]
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.0
+            .code_by_hash_ref(code_hash)
+            .map_err(|error| DatabaseError {
+                op: DbOp::CodeByHash(code_hash),
+                error,
+            })
+    }
+
+    #[inline]
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.0
+            .storage_ref(address, index)
+            .map_err(|error| DatabaseError {
+                op: DbOp::Storage { address, index },
+                error,
+            })
+    }
+
+    #[inline]
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.0
+            .block_hash_ref(number)
+            .map_err(|error| DatabaseError {
+                op: DbOp::BlockHash(number),
+                error,
+            })
+    }
+}
+
+impl<DB: DatabaseCommit> DatabaseCommit for WithErrorContext<DB> {
+    #[inline]
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        self.0.commit(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EVMError, InvalidTransaction};
+    use std::{format, string::ToString};
+
+    /// Database whose storage reads fail.
+    struct FailingStorage;
+
+    impl Database for FailingStorage {
+        type Error = &'static str;
+
+        fn basic(&mut self, _address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            Ok(None)
+        }
+
+        fn code_by_hash(&mut self, _code_hash: B256) -> Result<Bytecode, Self::Error> {
+            Ok(Bytecode::default())
+        }
+
+        fn storage(&mut self, _address: Address, _index: U256) -> Result<U256, Self::Error> {
+            Err("connection reset")
+        }
+
+        fn block_hash(&mut self, _number: u64) -> Result<B256, Self::Error> {
+            Ok(B256::ZERO)
+        }
+    }
+
+    #[test]
+    fn storage_error_names_the_failed_read() {
+        let address = Address::with_last_byte(0xaa);
+        let error = WithErrorContext(FailingStorage)
+            .storage(address, U256::from(7))
+            .unwrap_err();
+        assert_eq!(
+            error,
+            DatabaseError {
+                op: DbOp::Storage {
+                    address,
+                    index: U256::from(7),
+                },
+                error: "connection reset",
+            }
+        );
+        assert_eq!(
+            EVMError::<_, InvalidTransaction>::Database(error).to_string(),
+            format!("database error: storage({address}, 7) failed: connection reset")
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! Database that falls back to a secondary database for accounts missing from the primary.
+
+use crate::{
+    db::{Database, DatabaseCommit},
+    Account, AccountInfo, Address, Bytecode, HashMap, HashSet, B256, U256,
+};
+
+/// Error of a [`FallbackDb`], tagged with the database it originates from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FallbackDbError<PE, SE> {
+    /// Primary database error.
+    Primary(PE),
+    /// Secondary database error.
+    Secondary(SE),
+}
+
+/// Database that reads from `primary` and consults `secondary` on a miss.
+///
+/// A miss is an account that the primary reports as not existing (`Ok(None)`). Errors of
+/// the primary are returned as is and never fall back to the secondary.
+///
+/// Accounts looked up in the secondary, including the ones it does not have either, are
+/// cached together with their code and storage, so the secondary is queried at most once
+/// per account, slot and code hash. Storage of such accounts is read from the secondary,
+/// storage of all other accounts, code and block hashes from the primary. Slots committed
+/// to the primary are read from the primary from then on.
+#[derive(Clone, Debug, Default)]
+pub struct FallbackDb<P, S> {
+    /// Primary database.
+    pub primary: P,
+    /// Secondary database.
+    pub secondary: S,
+    /// Accounts looked up in the secondary.
+    accounts: HashMap<Address, Option<AccountInfo>>,
+    /// Accounts whose storage is read from the secondary.
+    from_secondary: HashSet<Address>,
+    /// Slots of [`Self::from_secondary`] accounts that were committed to the primary.
+    committed_slots: HashSet<(Address, U256)>,
+    /// Code of the accounts found in the secondary.
+    contracts: HashMap<B256, Bytecode>,
+    /// Storage of the accounts found in the secondary.
+    storage: HashMap<(Address, U256), U256>,
+}
+
+impl<P, S> FallbackDb<P, S> {
+    /// Creates a new database reading from `primary` and falling back to `secondary`.
+    pub fn new(primary: P, secondary: S) -> Self {
+        Self {
+            primary,
+            secondary,
+            accounts: HashMap::new(),
+            from_secondary: HashSet::new(),
+            committed_slots: HashSet::new(),
+            contracts: HashMap::new(),
+            storage: HashMap::new(),
+        }
+    }
+
+    /// Returns true if the account was served by the secondary database.
+    pub fn is_from_secondary(&self, address: &Address) -> bool {
+        self.from_secondary.contains(address)
+    }
+}
+
+impl<P: Database, S: Database> Database for FallbackDb<P, S> {
+    type Error = FallbackDbError<P::Error, S::Error>;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        if let Some(account) = self.primary.basic(address).map_err(Self::Error::Primary)? {
+            return Ok(Some(account));
+        }
+        if let Some(account) = self.accounts.get(&address) {
+            return Ok(account.clone());
+        }
+
+        let mut account = self
+            .secondary
+            .basic(address)
+            .map_err(Self::Error::Secondary)?;
+        if let Some(info) = &mut account {
+            if !info.is_empty_code_hash() {
+                let code = match info.code.take() {
+                    Some(code) => code,
+                    None => self
+                        .secondary
+                        .code_by_hash(info.code_hash)
+                        .map_err(Self::Error::Secondary)?,
+                };
+                info.code = Some(code.clone());
+                self.contracts.insert(info.code_hash, code);
+            }
+            self.from_secondary.insert(address);
+        }
+        self.accounts.insert(address, account.clone());
+        Ok(account)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if let Some(code) = self.contracts.get(&code_hash) {
+            return Ok(code.clone());
+        }
+        self.primary
+            .code_by_hash(code_hash)
+            .map_err(Self::Error::Primary)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        if !self.is_from_secondary(&address) || self.committed_slots.contains(&(address, index)) {
+            return self
+                .primary
+                .storage(address, index)
+                .map_err(Self::Error::Primary);
+        }
+        if let Some(value) = self.storage.get(&(address, index)) {
+            return Ok(*value);
+        }
+        let value = self
+            .secondary
+            .storage(address, index)
+            .map_err(Self::Error::Secondary)?;
+        self.storage.insert((address, index), value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.primary
+            .block_hash(number)
+            .map_err(Self::Error::Primary)
+    }
+}
+
+impl<P: DatabaseCommit, S> DatabaseCommit for FallbackDb<P, S> {
+    /// Commits the changes to the primary database.
+    ///
+    /// Committed slots are read from the primary afterwards, the other slots of accounts
+    /// served by the secondary are still read from the secondary. Storage of created or
+    /// self-destructed accounts is read from the primary only.
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        for (address, account) in &changes {
+            if !self.from_secondary.contains(address) {
+                continue;
+            }
+            if account.is_created() || account.is_selfdestructed() {
+                self.from_secondary.remove(address);
+                self.accounts.insert(*address, None);
+                self.storage
+                    .retain(|(slot_address, _), _| slot_address != address);
+                self.committed_slots
+                    .retain(|(slot_address, _)| slot_address != address);
+                continue;
+            }
+            for index in account.storage.keys() {
+                self.storage.remove(&(*address, *index));
+                self.committed_slots.insert((*address, *index));
+            }
+        }
+        self.primary.commit(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::EmptyDB, EvmStorageSlot};
+    use core::convert::Infallible;
+
+    /// Remote database with a single account that counts its lookups.
+    #[derive(Default)]
+    struct Remote {
+        address: Address,
+        basic_calls: usize,
+        storage_calls: usize,
+    }
+
+    impl Database for Remote {
+        type Error = Infallible;
+
+        fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            self.basic_calls += 1;
+            Ok((address == self.address).then(|| AccountInfo::from_balance(U256::from(10))))
+        }
+
+        fn code_by_hash(&mut self, _code_hash: B256) -> Result<Bytecode, Self::Error> {
+            Ok(Bytecode::default())
+        }
+
+        fn storage(&mut self, _address: Address, index: U256) -> Result<U256, Self::Error> {
+            self.storage_calls += 1;
+            Ok(index + U256::from(1))
+        }
+
+        fn block_hash(&mut self, _number: u64) -> Result<B256, Self::Error> {
+            Ok(B256::ZERO)
+        }
+    }
+
+    /// Local database that starts empty and keeps what is committed to it.
+    #[derive(Default)]
+    struct Local {
+        accounts: HashMap<Address, Account>,
+    }
+
+    impl Database for Local {
+        type Error = Infallible;
+
+        fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            Ok(self
+                .accounts
+                .get(&address)
+                .map(|account| account.info.clone()))
+        }
+
+        fn code_by_hash(&mut self, _code_hash: B256) -> Result<Bytecode, Self::Error> {
+            Ok(Bytecode::default())
+        }
+
+        fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+            Ok(self
+                .accounts
+                .get(&address)
+                .and_then(|account| account.storage.get(&index))
+                .map(|slot| slot.present_value)
+                .unwrap_or_default())
+        }
+
+        fn block_hash(&mut self, _number: u64) -> Result<B256, Self::Error> {
+            Ok(B256::ZERO)
+        }
+    }
+
+    impl DatabaseCommit for Local {
+        fn commit(&mut self, changes: HashMap<Address, Account>) {
+            self.accounts.extend(changes);
+        }
+    }
+
+    #[test]
+    fn falls_back_to_secondary_and_caches() {
+        let address = Address::with_last_byte(1);
+        let mut db = FallbackDb::new(
+            EmptyDB::new(),
+            Remote {
+                address,
+                ..Default::default()
+            },
+        );
+
+        for _ in 0..2 {
+            let account = db.basic(address).unwrap().unwrap();
+            assert_eq!(account.balance, U256::from(10));
+            assert_eq!(db.storage(address, U256::from(1)).unwrap(), U256::from(2));
+        }
+        assert!(db.is_from_secondary(&address));
+        assert_eq!(db.secondary.basic_calls, 1);
+        assert_eq!(db.secondary.storage_calls, 1);
+
+        // misses of both databases are cached too.
+        let missing = Address::with_last_byte(2);
+        assert_eq!(db.basic(missing).unwrap(), None);
+        assert_eq!(db.basic(missing).unwrap(), None);
+        assert!(!db.is_from_secondary(&missing));
+        assert_eq!(db.secondary.basic_calls, 2);
+    }
+
+    #[test]
+    fn commit_keeps_reading_uncommitted_slots_from_secondary() {
+        let address = Address::with_last_byte(1);
+        let mut db = FallbackDb::new(
+            Local::default(),
+            Remote {
+                address,
+                ..Default::default()
+            },
+        );
+
+        let mut account = Account::from(db.basic(address).unwrap().unwrap());
+        account.mark_touch();
+        account.storage.insert(
+            U256::from(1),
+            EvmStorageSlot::new_changed(U256::from(2), U256::from(7)),
+        );
+        db.commit(HashMap::from_iter([(address, account)]));
+
+        assert!(db.is_from_secondary(&address));
+        assert_eq!(db.storage(address, U256::from(1)).unwrap(), U256::from(7));
+        // a slot that was never loaded still comes from the secondary.
+        assert_eq!(db.storage(address, U256::from(5)).unwrap(), U256::from(6));
+        assert_eq!(db.secondary.storage_calls, 1);
+    }
+}
@@ -1,16 +1,23 @@
 use crate::{
     calc_blob_gasprice, AccessListItem, Account, Address, AuthorizationList, Block, Bytes,
     EvmWiring, InvalidHeader, InvalidTransaction, Spec, SpecId, Transaction, TransactionValidation,
-    B256, MAX_BLOB_NUMBER_PER_BLOCK, MAX_CODE_SIZE, MAX_INITCODE_SIZE, U256,
+    B256, BASE_FEE_MAX_CHANGE_DENOMINATOR, INITIAL_BASE_FEE, MAX_BLOB_NUMBER_PER_BLOCK,
+    MAX_BLOB_NUMBER_PER_BLOCK_PRAGUE, MAX_CODE_SIZE, MAX_INITCODE_SIZE, U256,
     VERSIONED_HASH_VERSION_KZG,
 };
 use alloy_primitives::TxKind;
-use core::cmp::{min, Ordering};
+use core::cmp::Ordering;
 use core::fmt::Debug;
 use core::hash::Hash;
 use std::boxed::Box;
 use std::vec::Vec;
 
+#[cfg(feature = "serde")]
+pub mod test_format;
+
+#[cfg(feature = "serde")]
+pub use test_format::TestEnv;
+
 /// Subtype
 pub type EnvWiring<EvmWiringT> =
     Env<<EvmWiringT as EvmWiring>::Block, <EvmWiringT as EvmWiring>::Transaction>;
@@ -37,14 +44,32 @@ impl<BlockT: Block, TxT: Transaction> Env<BlockT, TxT> {
     /// Calculates the effective gas price of the transaction.
     #[inline]
     pub fn effective_gas_price(&self) -> U256 {
-        let gas_price = self.tx.gas_price();
-        if let Some(priority_fee) = self.tx.max_priority_fee_per_gas() {
-            min(*gas_price, self.block.basefee() + priority_fee)
+        self.tx.effective_gas_price(self.settlement_base_fee())
+    }
+
+    /// Returns the base fee the transaction fees are settled against.
+    ///
+    /// This is zero if [`CfgEnv::allow_gas_price_below_base_fee`] is set, and the block base fee
+    /// otherwise.
+    #[inline]
+    pub fn settlement_base_fee(&self) -> U256 {
+        if self.cfg.allow_gas_price_below_base_fee {
+            U256::ZERO
         } else {
-            *gas_price
+            *self.block.basefee()
         }
     }
 
+    /// Returns the address the transaction fees are paid to.
+    ///
+    /// This is [`CfgEnv::fee_recipient`] if set, and the block coinbase otherwise.
+    #[inline]
+    pub fn fee_recipient(&self) -> Address {
+        self.cfg
+            .fee_recipient
+            .unwrap_or_else(|| *self.block.coinbase())
+    }
+
     /// Calculates the [EIP-4844] `data_fee` of the transaction.
     ///
     /// Returns `None` if `Cancun` is not enabled. This is enforced in [`Env::validate_block_env`].
@@ -77,6 +102,12 @@ impl<BlockT: Block, TxT: Transaction> Env<BlockT, TxT> {
         if SPEC::enabled(SpecId::MERGE) && self.block.prevrandao().is_none() {
             return Err(InvalidHeader::PrevrandaoNotSet);
         }
+        if SPEC::enabled(SpecId::MERGE)
+            && self.cfg.reject_post_merge_difficulty
+            && !self.block.difficulty().is_zero()
+        {
+            return Err(InvalidHeader::DifficultyNotZero);
+        }
         // `excess_blob_gas` is required for Cancun
         if SPEC::enabled(SpecId::CANCUN) && self.block.blob_excess_gas_and_price().is_none() {
             return Err(InvalidHeader::ExcessBlobGasNotSet);
@@ -84,6 +115,42 @@ impl<BlockT: Block, TxT: Transaction> Env<BlockT, TxT> {
         Ok(())
     }
 
+    /// Validate the block environment against the parent block.
+    ///
+    /// The timestamp has to be greater than `parent_timestamp`. From London on, the base fee
+    /// can differ from `parent_basefee` by at most the EIP-1559 maximum change of 1/8, and
+    /// without a parent base fee, as in the first London block, it has to be
+    /// [`INITIAL_BASE_FEE`]. The gas used by the parent is not known, so only these bounds
+    /// are checked and not the exact base fee.
+    pub fn validate_against_parent(
+        &self,
+        parent_timestamp: u64,
+        parent_basefee: Option<U256>,
+        spec: SpecId,
+    ) -> Result<(), InvalidHeader> {
+        if *self.block.timestamp() <= U256::from(parent_timestamp) {
+            return Err(InvalidHeader::TimestampNotAfterParent);
+        }
+        if !spec.is_enabled_in(SpecId::LONDON) {
+            return Ok(());
+        }
+
+        let basefee = *self.block.basefee();
+        let in_bounds = match parent_basefee {
+            None => basefee == U256::from(INITIAL_BASE_FEE),
+            Some(parent_basefee) => {
+                let max_change = parent_basefee / U256::from(BASE_FEE_MAX_CHANGE_DENOMINATOR);
+                let min = parent_basefee - max_change;
+                let max = parent_basefee.saturating_add(max_change.max(U256::from(1)));
+                (min..=max).contains(&basefee)
+            }
+        };
+        if !in_bounds {
+            return Err(InvalidHeader::BaseFeeChangeOutOfBounds);
+        }
+        Ok(())
+    }
+
     /// Validate transaction data that is set inside ENV and return error if something is wrong.
     ///
     /// Return initial spend gas (Gas needed to execute transaction).
@@ -117,24 +184,20 @@ impl<BlockT: Block, TxT: Transaction> Env<BlockT, TxT> {
                 }
             }
 
-            // check minimal cost against basefee
+            // check minimal cost against basefee, always met if fees are settled without it.
             if !self.cfg.is_base_fee_check_disabled()
-                && self.effective_gas_price() < *self.block.basefee()
+                && self.effective_gas_price() < self.settlement_base_fee()
             {
                 return Err(InvalidTransaction::GasPriceLessThanBasefee);
             }
         }
 
         // EIP-3860: Limit and meter initcode
-        if SPEC::enabled(SpecId::SHANGHAI) && self.tx.kind().is_create() {
-            let max_initcode_size = self
-                .cfg
-                .limit_contract_code_size
-                .map(|limit| limit.saturating_mul(2))
-                .unwrap_or(MAX_INITCODE_SIZE);
-            if self.tx.data().len() > max_initcode_size {
-                return Err(InvalidTransaction::CreateInitCodeSizeLimit);
-            }
+        if SPEC::enabled(SpecId::SHANGHAI)
+            && self.tx.kind().is_create()
+            && self.tx.data().len() > self.cfg.max_initcode_size()
+        {
+            return Err(InvalidTransaction::CreateInitCodeSizeLimit);
         }
 
         // - For before CANCUN, check that `blob_hashes` and `max_fee_per_blob_gas` are empty / not set
@@ -174,11 +237,17 @@ impl<BlockT: Block, TxT: Transaction> Env<BlockT, TxT> {
 
             // ensure the total blob gas spent is at most equal to the limit
             // assert blob_gas_used <= MAX_BLOB_GAS_PER_BLOCK
+            // EIP-7691 raises the limit in Prague.
+            let max_blobs = if SPEC::enabled(SpecId::PRAGUE) {
+                MAX_BLOB_NUMBER_PER_BLOCK_PRAGUE
+            } else {
+                MAX_BLOB_NUMBER_PER_BLOCK
+            } as usize;
             let num_blobs = self.tx.blob_hashes().len();
-            if num_blobs > MAX_BLOB_NUMBER_PER_BLOCK as usize {
+            if num_blobs > max_blobs {
                 return Err(InvalidTransaction::TooManyBlobs {
                     have: num_blobs,
-                    max: MAX_BLOB_NUMBER_PER_BLOCK as usize,
+                    max: max_blobs,
                 });
             }
         } else {
@@ -253,7 +322,10 @@ impl<BlockT: Block, TxT: Transaction> Env<BlockT, TxT> {
             .and_then(|gas_cost| gas_cost.checked_add(*self.tx.value()))
             .ok_or(InvalidTransaction::OverflowPaymentInTransaction)?;
 
-        if SPEC::enabled(SpecId::CANCUN) {
+        if self.cfg.is_fee_settlement_disabled() {
+            // only the value is transferred.
+            balance_check = *self.tx.value();
+        } else if SPEC::enabled(SpecId::CANCUN) {
             // if the tx is not a blob tx, this will be None, so we add zero
             let data_fee = self.calc_max_data_fee().unwrap_or_default();
             balance_check = balance_check
@@ -307,10 +379,79 @@ pub struct CfgEnv {
     /// If some it will effects EIP-170: Contract code size limit. Useful to increase this because of tests.
     /// By default it is 0x6000 (~25kb).
     pub limit_contract_code_size: Option<usize>,
+    /// If some it will effects EIP-3860: Limit and meter initcode, for transactions and for the
+    /// `CREATE` and `CREATE2` opcodes. Useful for experimenting with raised limits, like EIP-7907.
+    /// By default it is twice the contract code size limit, 0xC000 (~49kb) on mainnet.
+    pub limit_initcode_size: Option<usize>,
+    /// If some it overrides the interpreter stack limit of 1024 words, exceeding it halts with
+    /// [crate::HaltReason::StackOverflow]. Useful for experimenting with resized stacks.
+    ///
+    /// Any other limit breaks consensus. EOF code validation still uses the 1024 limit.
+    /// Limits above 16384 words are capped to it, as each call frame allocates its stack up
+    /// front. By default, it is set to `None`.
+    pub limit_stack_size: Option<usize>,
     /// Skips the nonce validation against the account's nonce:
     /// [`crate::InvalidTransaction::NonceTooHigh`] and
     /// [`crate::InvalidTransaction::NonceTooLow`]
     pub disable_nonce_check: bool,
+    /// Skips charging the caller for gas and paying the fees out. Gas is still metered and
+    /// reported, only the sender balance is required to cover the transferred value.
+    /// Useful for tracing historical transactions where the fee market does not matter.
+    /// By default, it is set to `false`.
+    pub disable_fee_settlement: bool,
+    /// Settles the fees as if the block base fee were zero. Transactions priced below the base
+    /// fee are accepted, the effective gas price of EIP-1559 transactions is clamped to
+    /// `min(max_fee_per_gas, max_priority_fee_per_gas)`, nothing is burned and the whole fee is
+    /// paid to the coinbase. The `BASEFEE` opcode still returns the block base fee.
+    /// Useful for replaying historical data that does not comply with EIP-1559 and for
+    /// permissive test networks.
+    ///
+    /// Unlike `disable_base_fee`, which only skips the validation and still settles the fees
+    /// against the block base fee, this changes the amounts that are charged and paid out.
+    /// If both are set, this takes precedence.
+    /// By default, it is set to `false`.
+    pub allow_gas_price_below_base_fee: bool,
+    /// If set, the EIP-1559 base fee is credited to this address instead of being burned.
+    /// Useful for test networks that pay the base fee to the coinbase.
+    /// By default, it is set to `None` and the base fee is burned.
+    pub base_fee_recipient: Option<Address>,
+    /// If set, the transaction fees (the priority fee since London) are paid to this address
+    /// instead of the block coinbase, for builders separating the fee recipient from the
+    /// block author. `COINBASE` still returns the block coinbase.
+    /// By default, it is set to `None` and the fees are paid to the coinbase.
+    pub fee_recipient: Option<Address>,
+    /// EIP-2929 cold and warm access costs for accounts and storage slots.
+    /// Chains that re-price state access can override them.
+    /// By default, mainnet costs are used.
+    pub access_gas_costs: AccessGasCosts,
+    /// Keccak-256 implementation used by the `KECCAK256` opcode.
+    /// By default, the builtin implementation is used.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub keccak: crate::EnvKeccak,
+    /// Maximum number of logs a transaction can emit. Logs of reverted frames are discarded
+    /// and do not count towards the limit.
+    ///
+    /// Emitting more halts execution with [crate::HaltReason::LogLimitExceeded].
+    /// By default, it is set to `None`, which means unlimited.
+    pub max_logs: Option<u64>,
+    /// Call depth at which the `call_depth_reached` inspector hook is called, each time a
+    /// frame is entered at this depth. The top level frame is at depth one. Useful for
+    /// flagging deep recursion well below the call stack limit of 1024.
+    ///
+    /// Execution is not halted.
+    /// By default, it is set to `None`.
+    pub call_depth_warning: Option<u64>,
+    /// Rejects post-merge blocks with a nonzero `difficulty` with
+    /// [`InvalidHeader::DifficultyNotZero`]. Such blocks are invalid but are sometimes
+    /// produced by tooling; `DIFFICULTY` returns `prevrandao` after the merge either way.
+    /// By default, it is set to `false` and the difficulty is ignored.
+    pub reject_post_merge_difficulty: bool,
+    /// Wall-clock deadline after which execution halts with
+    /// [crate::HaltReason::DeadlineExceeded].
+    /// By default, it is set to `None` and execution is not time limited.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub deadline: Option<ExecutionDeadline>,
     /// A hard memory limit in bytes beyond which [crate::result::OutOfGasError::Memory] cannot be resized.
     ///
     /// In cases where the gas limit may be extraordinarily high, it is recommended to set this to
@@ -354,6 +495,16 @@ impl CfgEnv {
         self.limit_contract_code_size.unwrap_or(MAX_CODE_SIZE)
     }
 
+    /// Returns max initcode size from [`Self::limit_initcode_size`] if set, twice
+    /// [`Self::limit_contract_code_size`] if that is set, or default [`MAX_INITCODE_SIZE`] value.
+    pub fn max_initcode_size(&self) -> usize {
+        self.limit_initcode_size.unwrap_or_else(|| {
+            self.limit_contract_code_size
+                .map(|limit| limit.saturating_mul(2))
+                .unwrap_or(MAX_INITCODE_SIZE)
+        })
+    }
+
     pub fn with_chain_id(mut self, chain_id: u64) -> Self {
         self.chain_id = chain_id;
         self
@@ -422,6 +573,10 @@ impl CfgEnv {
     pub const fn is_nonce_check_disabled(&self) -> bool {
         self.disable_nonce_check
     }
+
+    pub const fn is_fee_settlement_disabled(&self) -> bool {
+        self.disable_fee_settlement
+    }
 }
 
 impl Default for CfgEnv {
@@ -430,7 +585,20 @@ impl Default for CfgEnv {
             chain_id: 1,
             perf_analyse_created_bytecodes: AnalysisKind::default(),
             limit_contract_code_size: None,
+            limit_initcode_size: None,
+            limit_stack_size: None,
             disable_nonce_check: false,
+            disable_fee_settlement: false,
+            allow_gas_price_below_base_fee: false,
+            base_fee_recipient: None,
+            fee_recipient: None,
+            access_gas_costs: AccessGasCosts::default(),
+            keccak: crate::EnvKeccak::Default,
+            max_logs: None,
+            call_depth_warning: None,
+            reject_post_merge_difficulty: false,
+            #[cfg(feature = "std")]
+            deadline: None,
             #[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
             kzg_settings: crate::kzg::EnvKzgSettings::Default,
             #[cfg(feature = "memory_limit")]
@@ -494,9 +662,9 @@ pub struct BlockEnv {
 
 impl BlockEnv {
     /// Takes `blob_excess_gas` saves it inside env
-    /// and calculates `blob_fee` with [`BlobExcessGasAndPrice`].
-    pub fn set_blob_excess_gas_and_price(&mut self, excess_blob_gas: u64) {
-        self.blob_excess_gas_and_price = Some(BlobExcessGasAndPrice::new(excess_blob_gas));
+    /// and calculates `blob_fee` for the given spec with [`BlobExcessGasAndPrice`].
+    pub fn set_blob_excess_gas_and_price(&mut self, excess_blob_gas: u64, spec_id: SpecId) {
+        self.blob_excess_gas_and_price = Some(BlobExcessGasAndPrice::new(excess_blob_gas, spec_id));
     }
 }
 
@@ -552,7 +720,7 @@ impl Default for BlockEnv {
             basefee: U256::ZERO,
             difficulty: U256::ZERO,
             prevrandao: Some(B256::ZERO),
-            blob_excess_gas_and_price: Some(BlobExcessGasAndPrice::new(0)),
+            blob_excess_gas_and_price: Some(BlobExcessGasAndPrice::new(0, SpecId::LATEST)),
         }
     }
 }
@@ -735,9 +903,10 @@ pub struct BlobExcessGasAndPrice {
 }
 
 impl BlobExcessGasAndPrice {
-    /// Creates a new instance by calculating the blob gas price with [`calc_blob_gasprice`].
-    pub fn new(excess_blob_gas: u64) -> Self {
-        let blob_gasprice = calc_blob_gasprice(excess_blob_gas);
+    /// Creates a new instance by calculating the blob gas price of the given spec with
+    /// [`calc_blob_gasprice`].
+    pub fn new(excess_blob_gas: u64, spec_id: SpecId) -> Self {
+        let blob_gasprice = calc_blob_gasprice(excess_blob_gas, spec_id);
         Self {
             excess_blob_gas,
             blob_gasprice,
@@ -761,6 +930,81 @@ pub enum CreateScheme {
     },
 }
 
+/// EIP-2929 gas costs for account and storage slot access, applied from Berlin onwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccessGasCosts {
+    /// Cost of accessing an account that is not yet in the access list.
+    pub cold_account_access: u64,
+    /// Cost of accessing an account that is already in the access list.
+    pub warm_account_access: u64,
+    /// Cost of accessing a storage slot that is not yet in the access list.
+    pub cold_sload: u64,
+    /// Cost of accessing a storage slot that is already in the access list.
+    pub warm_sload: u64,
+}
+
+impl AccessGasCosts {
+    /// Ethereum mainnet costs.
+    pub const MAINNET: Self = Self {
+        cold_account_access: 2600,
+        warm_account_access: 100,
+        cold_sload: 2100,
+        warm_sload: 100,
+    };
+}
+
+impl Default for AccessGasCosts {
+    fn default() -> Self {
+        Self::MAINNET
+    }
+}
+
+/// Wall-clock deadline of an execution, see [`CfgEnv::deadline`].
+///
+/// The interpreter samples the clock every `check_interval` instructions and whenever a frame
+/// resumes after a call. Smaller intervals halt closer to the deadline at the cost of more
+/// clock reads.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ExecutionDeadline {
+    /// Instant after which execution halts.
+    pub at: std::time::Instant,
+    /// Number of instructions executed between clock reads. Zero is treated as one.
+    pub check_interval: u64,
+}
+
+#[cfg(feature = "std")]
+impl ExecutionDeadline {
+    /// Default number of instructions executed between clock reads.
+    pub const DEFAULT_CHECK_INTERVAL: u64 = 1024;
+
+    /// Creates a deadline at `at` with the default check interval.
+    pub fn new(at: std::time::Instant) -> Self {
+        Self {
+            at,
+            check_interval: Self::DEFAULT_CHECK_INTERVAL,
+        }
+    }
+
+    /// Creates a deadline `timeout` from now with the default check interval.
+    pub fn after(timeout: std::time::Duration) -> Self {
+        Self::new(std::time::Instant::now() + timeout)
+    }
+
+    /// Sets the number of instructions executed between clock reads.
+    pub fn with_check_interval(mut self, check_interval: u64) -> Self {
+        self.check_interval = check_interval;
+        self
+    }
+
+    /// Returns `true` if the deadline has passed.
+    #[inline]
+    pub fn is_exceeded(&self) -> bool {
+        std::time::Instant::now() >= self.at
+    }
+}
+
 /// What bytecode analysis to perform.
 #[derive(Clone, Default, Debug, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -776,6 +1020,28 @@ pub enum AnalysisKind {
 mod tests {
     use super::*;
 
+    #[test]
+    fn effective_gas_price_of_legacy_tx() {
+        let tx = TxEnv {
+            gas_price: U256::from(30),
+            ..Default::default()
+        };
+        assert_eq!(tx.effective_gas_price(U256::from(10)), U256::from(30));
+    }
+
+    #[test]
+    fn effective_gas_price_of_eip1559_tx() {
+        let tx = TxEnv {
+            gas_price: U256::from(100),
+            gas_priority_fee: Some(U256::from(5)),
+            ..Default::default()
+        };
+        // base fee plus priority fee is below the fee cap.
+        assert_eq!(tx.effective_gas_price(U256::from(90)), U256::from(95));
+        // the fee cap is below base fee plus priority fee.
+        assert_eq!(tx.effective_gas_price(U256::from(98)), U256::from(100));
+    }
+
     #[test]
     fn test_validate_tx_chain_id() {
         let mut env = Env::<BlockEnv, TxEnv>::default();
@@ -799,4 +1065,249 @@ mod tests {
             Err(InvalidTransaction::AccessListNotSupported)
         );
     }
+
+    #[test]
+    fn test_tx_target() {
+        let to = Address::with_last_byte(1);
+        let call = TxEnv {
+            transact_to: TxKind::Call(to),
+            ..Default::default()
+        };
+        assert!(!call.is_create());
+        assert_eq!(call.target(), Some(to));
+
+        let create = TxEnv {
+            transact_to: TxKind::Create,
+            ..Default::default()
+        };
+        assert!(create.is_create());
+        assert_eq!(create.target(), None);
+    }
+
+    #[test]
+    fn test_validate_block_env_prevrandao() {
+        let mut env = Env::<BlockEnv, TxEnv>::default();
+        env.block.prevrandao = None;
+        assert_eq!(env.validate_block_env::<crate::LondonSpec>(), Ok(()));
+        assert_eq!(
+            env.validate_block_env::<crate::MergeSpec>(),
+            Err(InvalidHeader::PrevrandaoNotSet)
+        );
+    }
+
+    #[test]
+    fn test_validate_block_env_post_merge_difficulty() {
+        let mut env = Env::<BlockEnv, TxEnv>::default();
+        env.block.prevrandao = Some(B256::ZERO);
+        env.block.difficulty = U256::from(1);
+        assert_eq!(env.validate_block_env::<crate::MergeSpec>(), Ok(()));
+
+        env.cfg.reject_post_merge_difficulty = true;
+        assert_eq!(
+            env.validate_block_env::<crate::MergeSpec>(),
+            Err(InvalidHeader::DifficultyNotZero)
+        );
+        assert_eq!(env.validate_block_env::<crate::LondonSpec>(), Ok(()));
+    }
+
+    #[test]
+    fn validate_against_parent_timestamp() {
+        let mut env = Env::<BlockEnv, TxEnv>::default();
+        env.block.timestamp = U256::from(10);
+        assert_eq!(env.validate_against_parent(9, None, SpecId::BERLIN), Ok(()));
+        for parent_timestamp in [10, 11] {
+            assert_eq!(
+                env.validate_against_parent(parent_timestamp, None, SpecId::BERLIN),
+                Err(InvalidHeader::TimestampNotAfterParent)
+            );
+        }
+    }
+
+    #[test]
+    fn validate_against_parent_basefee() {
+        let mut env = Env::<BlockEnv, TxEnv>::default();
+        env.block.timestamp = U256::from(10);
+        let parent_basefee = Some(U256::from(800));
+
+        for basefee in [700, 800, 900] {
+            env.block.basefee = U256::from(basefee);
+            assert_eq!(
+                env.validate_against_parent(9, parent_basefee, SpecId::LONDON),
+                Ok(())
+            );
+        }
+        for basefee in [699, 901] {
+            env.block.basefee = U256::from(basefee);
+            assert_eq!(
+                env.validate_against_parent(9, parent_basefee, SpecId::LONDON),
+                Err(InvalidHeader::BaseFeeChangeOutOfBounds)
+            );
+            // the base fee is not checked before London.
+            assert_eq!(
+                env.validate_against_parent(9, parent_basefee, SpecId::BERLIN),
+                Ok(())
+            );
+        }
+
+        // the first London block starts at the initial base fee.
+        assert_eq!(
+            env.validate_against_parent(9, None, SpecId::LONDON),
+            Err(InvalidHeader::BaseFeeChangeOutOfBounds)
+        );
+        env.block.basefee = U256::from(INITIAL_BASE_FEE);
+        assert_eq!(env.validate_against_parent(9, None, SpecId::LONDON), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_tx_initcode_size_limit() {
+        let mut env = Env::<BlockEnv, TxEnv>::default();
+        env.tx.transact_to = TxKind::Create;
+        env.tx.data = Bytes::from(vec![0; 50_000]);
+        env.tx.gas_limit = 30_000_000;
+        env.block.gas_limit = U256::from(30_000_000);
+
+        assert_eq!(env.validate_tx::<crate::LondonSpec>(), Ok(()));
+        assert_eq!(
+            env.validate_tx::<crate::ShanghaiSpec>(),
+            Err(InvalidTransaction::CreateInitCodeSizeLimit)
+        );
+    }
+
+    #[test]
+    fn test_validate_tx_raised_initcode_size_limit() {
+        let mut env = Env::<BlockEnv, TxEnv>::default();
+        env.tx.transact_to = TxKind::Create;
+        env.tx.data = Bytes::from(vec![0; MAX_INITCODE_SIZE + 1]);
+
+        assert_eq!(
+            env.validate_tx::<crate::ShanghaiSpec>(),
+            Err(InvalidTransaction::CreateInitCodeSizeLimit)
+        );
+        env.cfg.limit_initcode_size = Some(2 * MAX_INITCODE_SIZE);
+        assert_eq!(env.validate_tx::<crate::ShanghaiSpec>(), Ok(()));
+    }
+
+    #[cfg(feature = "optional_no_base_fee")]
+    #[test]
+    fn test_validate_tx_disabled_base_fee() {
+        let mut env = Env::<BlockEnv, TxEnv>::default();
+        env.block.basefee = U256::from(100);
+        env.tx.gas_price = U256::from(1);
+
+        assert_eq!(
+            env.validate_tx::<crate::LondonSpec>(),
+            Err(InvalidTransaction::GasPriceLessThanBasefee)
+        );
+        env.cfg.disable_base_fee = true;
+        assert_eq!(env.validate_tx::<crate::LondonSpec>(), Ok(()));
+    }
+
+    #[cfg(feature = "optional_block_gas_limit")]
+    #[test]
+    fn test_validate_tx_disabled_block_gas_limit() {
+        let mut env = Env::<BlockEnv, TxEnv>::default();
+        env.block.gas_limit = U256::from(30_000);
+        env.tx.gas_limit = 50_000;
+
+        assert_eq!(
+            env.validate_tx::<crate::LatestSpec>(),
+            Err(InvalidTransaction::CallerGasLimitMoreThanBlock)
+        );
+        env.cfg.disable_block_gas_limit = true;
+        assert_eq!(env.validate_tx::<crate::LatestSpec>(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_tx_against_state_nonce_gap() {
+        let mut env = Env::<BlockEnv, TxEnv>::default();
+        let mut account = Account::from(crate::AccountInfo {
+            nonce: 1,
+            ..Default::default()
+        });
+
+        env.tx.nonce = 1;
+        assert_eq!(
+            env.validate_tx_against_state::<crate::LatestSpec>(&mut account),
+            Ok(())
+        );
+        env.tx.nonce = 2;
+        assert_eq!(
+            env.validate_tx_against_state::<crate::LatestSpec>(&mut account),
+            Err(InvalidTransaction::NonceTooHigh { tx: 2, state: 1 })
+        );
+    }
+
+    #[cfg(feature = "optional_balance_check")]
+    #[test]
+    fn test_validate_tx_against_state_disabled_balance_check() {
+        let mut env = Env::<BlockEnv, TxEnv>::default();
+        env.tx.gas_limit = 21_000;
+        env.tx.gas_price = U256::from(1);
+        let mut account = Account::from(crate::AccountInfo::default());
+
+        assert_eq!(
+            env.validate_tx_against_state::<crate::LatestSpec>(&mut account),
+            Err(InvalidTransaction::LackOfFundForMaxFee {
+                fee: Box::new(U256::from(21_000)),
+                balance: Box::new(U256::ZERO),
+            })
+        );
+        env.cfg.disable_balance_check = true;
+        assert_eq!(
+            env.validate_tx_against_state::<crate::LatestSpec>(&mut account),
+            Ok(())
+        );
+        // the caller is credited the cost of the transaction.
+        assert_eq!(account.info.balance, U256::from(21_000));
+    }
+
+    fn blob_tx_env(blob_hashes: Vec<B256>) -> Env<BlockEnv, TxEnv> {
+        let mut env = Env::<BlockEnv, TxEnv>::default();
+        env.tx.transact_to = TxKind::Call(Address::with_last_byte(1));
+        env.tx.max_fee_per_blob_gas = Some(U256::from(1));
+        env.tx.blob_hashes = blob_hashes;
+        env
+    }
+
+    #[test]
+    fn test_validate_tx_blob_versioned_hash_version() {
+        let mut hash = B256::ZERO;
+        hash[0] = VERSIONED_HASH_VERSION_KZG;
+        let env = blob_tx_env(vec![hash]);
+        assert_eq!(env.validate_tx::<crate::CancunSpec>(), Ok(()));
+
+        hash[0] = 0x02;
+        let env = blob_tx_env(vec![hash]);
+        assert_eq!(
+            env.validate_tx::<crate::CancunSpec>(),
+            Err(InvalidTransaction::BlobVersionNotSupported)
+        );
+    }
+
+    #[test]
+    fn test_validate_tx_too_many_blobs() {
+        let mut hash = B256::ZERO;
+        hash[0] = VERSIONED_HASH_VERSION_KZG;
+        let max = MAX_BLOB_NUMBER_PER_BLOCK as usize;
+
+        let env = blob_tx_env(vec![hash; max]);
+        assert_eq!(env.validate_tx::<crate::CancunSpec>(), Ok(()));
+
+        let env = blob_tx_env(vec![hash; max + 1]);
+        assert_eq!(
+            env.validate_tx::<crate::CancunSpec>(),
+            Err(InvalidTransaction::TooManyBlobs { max, have: max + 1 })
+        );
+
+        // EIP-7691 raises the limit in Prague.
+        let max = MAX_BLOB_NUMBER_PER_BLOCK_PRAGUE as usize;
+        let env = blob_tx_env(vec![hash; max]);
+        assert_eq!(env.validate_tx::<crate::PragueSpec>(), Ok(()));
+
+        let env = blob_tx_env(vec![hash; max + 1]);
+        assert_eq!(
+            env.validate_tx::<crate::PragueSpec>(),
+            Err(InvalidTransaction::TooManyBlobs { max, have: max + 1 })
+        );
+    }
 }
@@ -0,0 +1,146 @@
+//! Environment in the JSON format of the Ethereum tests.
+
+use crate::{
+    calc_excess_blob_gas, Address, BlobExcessGasAndPrice, BlockEnv, CfgEnv, Env, SpecId, TxEnv,
+    B256, U256,
+};
+
+/// Block environment in the JSON format of the Ethereum tests, the `env` object of state
+/// tests.
+///
+/// Quantities are hex encoded. Fields introduced after Frontier are optional, the blob gas
+/// fields are only present since Cancun.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestEnv {
+    /// Coinbase of the block.
+    pub current_coinbase: Address,
+    /// Difficulty of the block.
+    #[serde(default)]
+    pub current_difficulty: U256,
+    /// Gas limit of the block.
+    pub current_gas_limit: U256,
+    /// Number of the block.
+    pub current_number: U256,
+    /// Timestamp of the block.
+    pub current_timestamp: U256,
+    /// Base fee of the block, since London.
+    pub current_base_fee: Option<U256>,
+    /// Hash of the parent block.
+    pub previous_hash: Option<B256>,
+    /// Output of the randomness beacon, since Paris.
+    pub current_random: Option<B256>,
+    /// Parent beacon block root, since Cancun.
+    pub current_beacon_root: Option<B256>,
+    /// Withdrawals root of the block, since Shanghai.
+    pub current_withdrawals_root: Option<B256>,
+    /// Blob gas used by the parent block, since Cancun.
+    pub parent_blob_gas_used: Option<U256>,
+    /// Excess blob gas of the parent block, since Cancun.
+    pub parent_excess_blob_gas: Option<U256>,
+    /// Excess blob gas of the block, since Cancun.
+    pub current_excess_blob_gas: Option<U256>,
+}
+
+impl TestEnv {
+    /// Returns the excess blob gas of the block, calculated from the parent block under
+    /// `spec_id` if it is not given.
+    pub fn excess_blob_gas(&self, spec_id: SpecId) -> Option<u64> {
+        if let Some(excess_blob_gas) = self.current_excess_blob_gas {
+            return Some(excess_blob_gas.saturating_to());
+        }
+        let (Some(parent_excess_blob_gas), Some(parent_blob_gas_used)) =
+            (self.parent_excess_blob_gas, self.parent_blob_gas_used)
+        else {
+            return None;
+        };
+        Some(calc_excess_blob_gas(
+            parent_excess_blob_gas.saturating_to(),
+            parent_blob_gas_used.saturating_to(),
+            spec_id,
+        ))
+    }
+
+    /// Returns the block environment. The blob gas price depends on `spec_id`.
+    pub fn block_env(&self, spec_id: SpecId) -> BlockEnv {
+        BlockEnv {
+            number: self.current_number,
+            coinbase: self.current_coinbase,
+            timestamp: self.current_timestamp,
+            gas_limit: self.current_gas_limit,
+            basefee: self.current_base_fee.unwrap_or_default(),
+            difficulty: self.current_difficulty,
+            prevrandao: self.current_random,
+            blob_excess_gas_and_price: self
+                .excess_blob_gas(spec_id)
+                .map(|excess_blob_gas| BlobExcessGasAndPrice::new(excess_blob_gas, spec_id)),
+        }
+    }
+
+    /// Returns the environment with the [block environment](Self::block_env), the default
+    /// configuration of mainnet and the default transaction.
+    pub fn env(&self, spec_id: SpecId) -> Env<BlockEnv, TxEnv> {
+        Env {
+            cfg: CfgEnv::default(),
+            block: self.block_env(spec_id),
+            tx: TxEnv::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, b256};
+
+    #[test]
+    fn deserializes_cancun_state_test_env() {
+        let json = r#"{
+            "currentBaseFee": "0x07",
+            "currentBeaconRoot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "currentCoinbase": "0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba",
+            "currentDifficulty": "0x00",
+            "currentExcessBlobGas": "0x0e0000",
+            "currentGasLimit": "0x016345785d8a0000",
+            "currentNumber": "0x01",
+            "currentRandom": "0x0000000000000000000000000000000000000000000000000000000000020000",
+            "currentTimestamp": "0x03e8",
+            "currentWithdrawalsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "previousHash": "0x5e20a0453cecd065ea59c37ac63e079ee08998b6045136a8ce6635c7912ec0b6"
+        }"#;
+        let test_env: TestEnv = serde_json::from_str(json).unwrap();
+        let env = test_env.env(SpecId::CANCUN);
+
+        assert_eq!(env.cfg.chain_id, 1);
+        assert_eq!(
+            env.block,
+            BlockEnv {
+                number: U256::from(1),
+                coinbase: address!("2adc25665018aa1fe0e6bc666dac8fc2697ff9ba"),
+                timestamp: U256::from(1000),
+                gas_limit: U256::from(100_000_000_000_000_000u64),
+                basefee: U256::from(7),
+                difficulty: U256::ZERO,
+                prevrandao: Some(b256!(
+                    "0000000000000000000000000000000000000000000000000000000000020000"
+                )),
+                blob_excess_gas_and_price: Some(BlobExcessGasAndPrice::new(
+                    0x0e0000,
+                    SpecId::CANCUN
+                )),
+            }
+        );
+
+        // without the excess blob gas it is calculated from the parent block, which used
+        // exactly the target.
+        let test_env = TestEnv {
+            current_excess_blob_gas: None,
+            parent_excess_blob_gas: Some(U256::from(0x0e0000)),
+            parent_blob_gas_used: Some(U256::from(0x060000)),
+            ..test_env
+        };
+        assert_eq!(test_env.excess_blob_gas(SpecId::CANCUN), Some(0x0e0000));
+        // which is below the Prague target.
+        assert_eq!(test_env.excess_blob_gas(SpecId::PRAGUE), Some(0x080000));
+    }
+}
@@ -0,0 +1,50 @@
+//! Pluggable Keccak-256 implementation.
+
+use crate::{keccak256, B256, KECCAK_EMPTY};
+use core::fmt;
+use std::sync::Arc;
+
+/// Keccak-256 implementation that can be plugged into the interpreter with [`EnvKeccak`].
+pub trait KeccakBackend: fmt::Debug + Send + Sync {
+    /// Returns the Keccak-256 hash of `input`.
+    fn keccak256(&self, input: &[u8]) -> B256;
+}
+
+/// Keccak-256 implementation used by the `KECCAK256` opcode.
+///
+/// Other hashing (code hashes, `CREATE2` addresses) always uses [`keccak256`].
+#[derive(Clone, Debug, Default)]
+pub enum EnvKeccak {
+    /// Builtin implementation, [`keccak256`].
+    #[default]
+    Default,
+    /// Custom implementation.
+    Custom(Arc<dyn KeccakBackend>),
+}
+
+impl EnvKeccak {
+    /// Returns the Keccak-256 hash of `input`.
+    ///
+    /// The builtin implementation returns [`KECCAK_EMPTY`] for empty input without hashing.
+    #[inline]
+    pub fn hash(&self, input: &[u8]) -> B256 {
+        match self {
+            Self::Default if input.is_empty() => KECCAK_EMPTY,
+            Self::Default => keccak256(input),
+            Self::Custom(backend) => backend.keccak256(input),
+        }
+    }
+}
+
+impl PartialEq for EnvKeccak {
+    /// Custom implementations are equal if they are the same instance.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Default, Self::Default) => true,
+            (Self::Custom(a), Self::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for EnvKeccak {}
@@ -12,9 +12,11 @@ pub mod db;
 pub mod eip7702;
 pub mod env;
 
+mod access_list;
 mod bytecode;
 mod constants;
 mod evm_wiring;
+mod keccak;
 #[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
 pub mod kzg;
 pub mod precompile;
@@ -23,9 +25,10 @@ pub mod specification;
 pub mod state;
 pub mod transaction;
 pub mod utilities;
+pub use access_list::AccessListBuilder;
 pub use alloy_eips::eip2930::{AccessList, AccessListItem};
 pub use alloy_primitives::{
-    self, address, b256, bytes, fixed_bytes, hex, hex_literal, ruint, uint, Address, Bytes,
+    self, address, b256, bytes, fixed_bytes, hex, hex_literal, ruint, uint, Address, Bloom, Bytes,
     FixedBytes, Log, LogData, TxKind, B256, I256, U256,
 };
 pub use bitvec;
@@ -37,6 +40,7 @@ pub use eip7702::{
 };
 pub use env::*;
 pub use evm_wiring::*;
+pub use keccak::{EnvKeccak, KeccakBackend};
 
 cfg_if::cfg_if! {
     if #[cfg(all(not(feature = "hashbrown"), feature = "std"))] {
@@ -155,7 +155,15 @@ impl fmt::Display for PrecompileErrors {
 pub enum PrecompileError {
     /// out of gas is the main error. Others are here just for completeness
     OutOfGas,
+    /// The input of a fixed-length precompile has the wrong length.
+    InvalidInputLength {
+        /// Required input length in bytes.
+        expected: usize,
+        /// Provided input length in bytes.
+        got: usize,
+    },
     // Blake2 errors
+    #[deprecated = "fixed-length precompiles return `InvalidInputLength` instead"]
     Blake2WrongLength,
     Blake2WrongFinalIndicatorFlag,
     // Modexp errors
@@ -168,6 +176,7 @@ pub enum PrecompileError {
     Bn128PairLength,
     // Blob errors
     /// The input length is not exactly 192 bytes.
+    #[deprecated = "fixed-length precompiles return `InvalidInputLength` instead"]
     BlobInvalidInputLength,
     /// The commitment does not match the versioned hash.
     BlobMismatchedVersion,
@@ -198,9 +207,16 @@ impl From<PrecompileError> for PrecompileErrors {
 impl core::error::Error for PrecompileError {}
 
 impl fmt::Display for PrecompileError {
+    #[allow(deprecated)]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
             Self::OutOfGas => "out of gas",
+            Self::InvalidInputLength { expected, got } => {
+                return write!(
+                    f,
+                    "invalid input length: expected {expected} bytes, got {got}"
+                );
+            }
             Self::Blake2WrongLength => "wrong input length for blake2",
             Self::Blake2WrongFinalIndicatorFlag => "wrong final indicator flag for blake2",
             Self::ModexpExpOverflow => "modexp exp overflow",
@@ -25,6 +25,84 @@ pub struct ResultAndState<HaltReasonT: HaltReasonTrait> {
     pub result: ExecutionResult<HaltReasonT>,
     /// State that got updated
     pub state: EvmState,
+    /// Gas accounting of the transaction.
+    pub gas: ResultGas,
+    /// Value received by the block coinbase: the fees it was paid plus the value transferred
+    /// to it during execution. The burned base fee is not included, and neither are fees paid
+    /// to a different [`crate::CfgEnv::fee_recipient`].
+    pub coinbase_reward: U256,
+    /// Value created by the transaction outside of execution, e.g. the mint of an Optimism
+    /// deposit. Zero on mainnet.
+    pub minted: U256,
+    /// Fees burned by the transaction: the EIP-1559 base fee, unless it is credited to
+    /// [`crate::CfgEnv::base_fee_recipient`]. Chains that pay the base fee out, like Optimism,
+    /// burn nothing.
+    pub burned: U256,
+    /// Accounts deleted by `SELFDESTRUCT`, in execution order.
+    ///
+    /// Self-destructs of reverted frames are not included. Since
+    /// [EIP-6780](https://eips.ethereum.org/EIPS/eip-6780) (Cancun) only accounts created in
+    /// the same transaction are deleted, the self-destructs of other accounts only transfer
+    /// their balance and are not included.
+    pub selfdestructed: Vec<SelfDestructRecord>,
+}
+
+impl<HaltReasonT: HaltReasonTrait> ResultAndState<HaltReasonT> {
+    /// Returns the length of the runtime code deployed by a successful contract creation.
+    ///
+    /// This is the length the code deposit cost of 200 gas per byte is charged for.
+    pub fn created_code_len(&self) -> Option<usize> {
+        let address = self.result.created_address()?;
+        let code = self.state.get(&address)?.info.code.as_ref()?;
+        Some(code.len())
+    }
+}
+
+/// A `SELFDESTRUCT` that deleted an account.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SelfDestructRecord {
+    /// The self-destructed account.
+    pub address: Address,
+    /// The beneficiary the balance was sent to.
+    pub target: Address,
+    /// The balance of the account at the time of the self-destruct.
+    ///
+    /// It is transferred to `target`, or burned if `target` is the account itself.
+    pub balance: U256,
+}
+
+/// Gas accounting of a transaction, split into intrinsic and execution gas.
+///
+/// `intrinsic_gas + execution_gas - refund` equals the `gas_used` of the [`ExecutionResult`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResultGas {
+    /// Whether the transaction is a contract creation.
+    pub is_create: bool,
+    /// Intrinsic gas charged before execution, see `initial_tx_gas`.
+    pub intrinsic_gas: u64,
+    /// Gas spent by the execution of the call or creation, before refunds.
+    pub execution_gas: u64,
+    /// Refunded gas.
+    pub refund: u64,
+}
+
+impl ResultGas {
+    /// Creates the gas accounting from the intrinsic gas, the final used gas and the refund.
+    pub const fn new(is_create: bool, intrinsic_gas: u64, gas_used: u64, refund: u64) -> Self {
+        Self {
+            is_create,
+            intrinsic_gas,
+            execution_gas: (gas_used + refund).saturating_sub(intrinsic_gas),
+            refund,
+        }
+    }
+
+    /// Returns the gas used, `intrinsic_gas + execution_gas - refund`.
+    pub const fn gas_used(&self) -> u64 {
+        self.intrinsic_gas + self.execution_gas - self.refund
+    }
 }
 
 /// Result of a transaction execution.
@@ -73,6 +151,27 @@ impl<HaltReasonT: HaltReasonTrait> ExecutionResult<HaltReasonT> {
         }
     }
 
+    /// Returns the message of a revert with the Solidity `Error(string)` error.
+    ///
+    /// Returns `None` if the execution did not revert or the output is not an ABI encoded
+    /// `Error(string)`, see [`Self::output`] for the raw revert data.
+    pub fn revert_reason(&self) -> Option<String> {
+        match self {
+            Self::Revert { output, .. } => decode_revert_reason(output),
+            _ => None,
+        }
+    }
+
+    /// Returns the address of the created contract.
+    ///
+    /// Returns `None` if the execution was not a successful contract creation.
+    pub fn created_address(&self) -> Option<Address> {
+        match self {
+            Self::Success { output, .. } => output.address().copied(),
+            _ => None,
+        }
+    }
+
     /// Consumes the type and returns the output data of the execution.
     ///
     /// Returns `None` if the execution was halted.
@@ -110,6 +209,23 @@ impl<HaltReasonT: HaltReasonTrait> ExecutionResult<HaltReasonT> {
     }
 }
 
+/// Selector of the Solidity `Error(string)` error.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Decodes the message of an ABI encoded `Error(string)`.
+fn decode_revert_reason(output: &[u8]) -> Option<String> {
+    let data = output.strip_prefix(&ERROR_SELECTOR)?;
+    let word = |offset: usize| -> Option<usize> {
+        let word = data.get(offset..offset.checked_add(32)?)?;
+        U256::from_be_slice(word).try_into().ok()
+    };
+    let offset = word(0)?;
+    let len = word(offset)?;
+    let start = offset.checked_add(32)?;
+    let message = data.get(start..start.checked_add(len)?)?;
+    String::from_utf8(message.to_vec()).ok()
+}
+
 /// Output of a transaction execution.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -383,8 +499,15 @@ impl fmt::Display for InvalidTransaction {
 pub enum InvalidHeader {
     /// `prevrandao` is not set for Merge and above.
     PrevrandaoNotSet,
+    /// `difficulty` is not zero for Merge and above, see
+    /// [`crate::CfgEnv::reject_post_merge_difficulty`].
+    DifficultyNotZero,
     /// `excess_blob_gas` is not set for Cancun and above.
     ExcessBlobGasNotSet,
+    /// `timestamp` is not greater than the parent block timestamp.
+    TimestampNotAfterParent,
+    /// `basefee` differs from the parent base fee by more than EIP-1559 allows.
+    BaseFeeChangeOutOfBounds,
 }
 
 impl core::error::Error for InvalidHeader {}
@@ -393,7 +516,12 @@ impl fmt::Display for InvalidHeader {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::PrevrandaoNotSet => write!(f, "`prevrandao` not set"),
+            Self::DifficultyNotZero => write!(f, "`difficulty` not zero after the merge"),
             Self::ExcessBlobGasNotSet => write!(f, "`excess_blob_gas` not set"),
+            Self::TimestampNotAfterParent => write!(f, "`timestamp` not after parent timestamp"),
+            Self::BaseFeeChangeOutOfBounds => {
+                write!(f, "`basefee` change from parent out of bounds")
+            }
         }
     }
 }
@@ -446,6 +574,12 @@ pub enum HaltReason {
     EOFFunctionStackOverflow,
     /// Check for target address validity is only done inside subcall.
     InvalidEXTCALLTarget,
+    /// Opcode budget set by an inspector was exhausted.
+    OpcodeBudgetExceeded,
+    /// Transaction emitted more logs than allowed by [`CfgEnv::max_logs`](crate::CfgEnv::max_logs).
+    LogLimitExceeded,
+    /// Execution ran past [`CfgEnv::deadline`](crate::CfgEnv::deadline).
+    DeadlineExceeded,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -462,4 +596,6 @@ pub enum OutOfGasError {
     // When performing something that takes a U256 and casts down to a u64, if its too large this would fire
     // i.e. in `as_usize_or_fail`
     InvalidOperand,
+    // EIP-2200: SSTORE with gas left at or below the call stipend
+    ReentrancySentry,
 }
@@ -29,6 +29,9 @@ pub enum SpecId {
     CANCUN = 17,          // Cancun                 19426587 (Timestamp: 1710338135)
     PRAGUE = 18,          // Prague                 TBD
     PRAGUE_EOF = 19,      // Prague+EOF             TBD
+    /// Every feature, opcode and precompile of all named forks.
+    ///
+    /// Its value is `u8::MAX`, so it enables all other specs in [`SpecId::is_enabled_in`].
     #[default]
     LATEST = u8::MAX,
 }
@@ -41,6 +44,8 @@ impl SpecId {
     }
 
     /// Returns `true` if the given specification ID is enabled in this spec.
+    ///
+    /// Specs are ordered, so this is `true` for every `other` when `self` is [`SpecId::LATEST`].
     #[inline]
     pub const fn is_enabled_in(self, other: Self) -> bool {
         Self::enabled(self, other)
@@ -250,10 +255,50 @@ macro_rules! spec_to_generic {
     }};
 }
 
+/// Computation that is generic over the [`Spec`], run with [`with_spec`].
+///
+/// Closures cannot be generic, this is the function-based alternative to
+/// [`spec_to_generic!`] for code that needs to return a value.
+pub trait SpecFn {
+    /// Value returned for every spec.
+    type Output;
+
+    /// Runs the computation with `SPEC` in scope.
+    fn call<SPEC: Spec>(self) -> Self::Output;
+}
+
+/// Runs `f` with the [`Spec`] matching `spec_id` and returns its output.
+#[inline]
+pub fn with_spec<F: SpecFn>(spec_id: SpecId, f: F) -> F::Output {
+    spec_to_generic!(spec_id, f.call::<SPEC>())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn with_spec_returns_value() {
+        struct SpecIdOf;
+
+        impl SpecFn for SpecIdOf {
+            type Output = SpecId;
+
+            fn call<SPEC: Spec>(self) -> SpecId {
+                SPEC::SPEC_ID
+            }
+        }
+
+        for (spec_id, expected) in [
+            (SpecId::FRONTIER, SpecId::FRONTIER),
+            (SpecId::MUIR_GLACIER, SpecId::ISTANBUL),
+            (SpecId::CANCUN, SpecId::CANCUN),
+            (SpecId::LATEST, SpecId::LATEST),
+        ] {
+            assert_eq!(with_spec(spec_id, SpecIdOf), expected);
+        }
+    }
+
     #[test]
     fn spec_to_generic() {
         use SpecId::*;
@@ -279,4 +324,11 @@ mod tests {
         spec_to_generic!(PRAGUE_EOF, assert_eq!(SPEC::SPEC_ID, PRAGUE_EOF));
         spec_to_generic!(LATEST, assert_eq!(SPEC::SPEC_ID, LATEST));
     }
+
+    #[test]
+    fn latest_enables_every_spec() {
+        for spec in (0..=u8::MAX).filter_map(SpecId::try_from_u8) {
+            assert!(LATEST.is_enabled_in(spec));
+        }
+    }
 }
@@ -294,6 +294,17 @@ impl AccountInfo {
         !self.is_empty()
     }
 
+    /// Returns `true` if the account is externally owned.
+    ///
+    /// An account is an EOA if it has no code or if its code is an EIP-7702 delegation
+    /// designator. The code needs to be loaded to recognize a delegated account, an account
+    /// with a non-empty code hash and no code is not an EOA.
+    pub fn is_eoa(&self) -> bool {
+        self.is_empty_code_hash()
+            || self.code_hash.is_zero()
+            || self.code.as_ref().is_some_and(Bytecode::is_eip7702)
+    }
+
     /// Returns `true` if account has no nonce and code.
     pub fn has_no_code_and_nonce(&self) -> bool {
         self.is_empty_code_hash() && self.nonce == 0
@@ -337,7 +348,7 @@ impl AccountInfo {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Account, KECCAK_EMPTY, U256};
+    use crate::{Account, AccountInfo, Address, Bytecode, Bytes, KECCAK_EMPTY, U256};
 
     #[test]
     fn account_is_empty_balance() {
@@ -378,6 +389,23 @@ mod tests {
         assert!(account.is_empty());
     }
 
+    #[test]
+    fn account_info_is_eoa() {
+        let empty = AccountInfo::default();
+        assert!(empty.is_empty());
+        assert!(empty.is_eoa());
+
+        let contract =
+            AccountInfo::from_bytecode(Bytecode::new_legacy(Bytes::from_static(&[0x60, 0x00])));
+        assert!(!contract.is_empty());
+        assert!(!contract.is_eoa());
+
+        let delegated = AccountInfo::from_bytecode(Bytecode::new_eip7702(Address::ZERO));
+        assert!(!delegated.is_empty());
+        assert!(delegated.is_eoa());
+        assert!(!delegated.without_code().is_eoa());
+    }
+
     #[test]
     fn account_state() {
         let mut account = Account::default();
@@ -55,6 +55,30 @@ pub trait Transaction {
     /// [EIP-Set EOA account code for one transaction](https://eips.ethereum.org/EIPS/eip-7702)
     fn authorization_list(&self) -> Option<&AuthorizationList>;
 
+    /// Returns `true` if the transaction creates a contract.
+    fn is_create(&self) -> bool {
+        self.kind().is_create()
+    }
+
+    /// Returns the address the transaction calls, or `None` for contract creation.
+    fn target(&self) -> Option<Address> {
+        self.kind().to().copied()
+    }
+
+    /// Returns the gas price paid in a block with the given `base_fee`.
+    ///
+    /// For [EIP-1559] transactions this is `min(max_fee_per_gas, base_fee + max_priority_fee_per_gas)`,
+    /// for legacy transactions the gas price.
+    ///
+    /// [EIP-1559]: https://eips.ethereum.org/EIPS/eip-1559
+    fn effective_gas_price(&self, base_fee: U256) -> U256 {
+        let gas_price = *self.gas_price();
+        match self.max_priority_fee_per_gas() {
+            Some(priority_fee) => gas_price.min(base_fee.saturating_add(*priority_fee)),
+            None => gas_price,
+        }
+    }
+
     /// See [EIP-4844], [`crate::Env::calc_data_fee`], and [`crate::Env::calc_max_data_fee`].
     ///
     /// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
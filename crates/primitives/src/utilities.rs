@@ -1,5 +1,7 @@
 use crate::{
-    b256, B256, BLOB_GASPRICE_UPDATE_FRACTION, MIN_BLOB_GASPRICE, TARGET_BLOB_GAS_PER_BLOCK,
+    b256, Bloom, Log, SpecId, B256, BLOB_GASPRICE_UPDATE_FRACTION,
+    BLOB_GASPRICE_UPDATE_FRACTION_PRAGUE, MIN_BLOB_GASPRICE, TARGET_BLOB_GAS_PER_BLOCK,
+    TARGET_BLOB_GAS_PER_BLOCK_PRAGUE,
 };
 pub use alloy_primitives::keccak256;
 
@@ -7,26 +9,56 @@ pub use alloy_primitives::keccak256;
 pub const KECCAK_EMPTY: B256 =
     b256!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
 
+/// Calculates the logs bloom of `logs`, e.g. of all receipts of a block for its header.
+///
+/// Each log sets three bits of the 2048-bit filter for its address and for each of its
+/// topics, taken from the keccak hash of the value (`M3:2048` in the yellow paper).
+#[inline]
+pub fn logs_bloom<'a>(logs: impl IntoIterator<Item = &'a Log>) -> Bloom {
+    let mut bloom = Bloom::ZERO;
+    for log in logs {
+        bloom.accrue_log(log);
+    }
+    bloom
+}
+
 /// Calculates the `excess_blob_gas` from the parent header's `blob_gas_used` and `excess_blob_gas`.
 ///
+/// `spec_id` is the spec of the block whose excess blob gas is calculated. Prague uses the
+/// blob target of [EIP-7691](https://eips.ethereum.org/EIPS/eip-7691), earlier specs the one of
+/// EIP-4844.
+///
 /// See also [the EIP-4844 helpers]<https://eips.ethereum.org/EIPS/eip-4844#helpers>
 /// (`calc_excess_blob_gas`).
 #[inline]
-pub fn calc_excess_blob_gas(parent_excess_blob_gas: u64, parent_blob_gas_used: u64) -> u64 {
-    (parent_excess_blob_gas + parent_blob_gas_used).saturating_sub(TARGET_BLOB_GAS_PER_BLOCK)
+pub fn calc_excess_blob_gas(
+    parent_excess_blob_gas: u64,
+    parent_blob_gas_used: u64,
+    spec_id: SpecId,
+) -> u64 {
+    let target_blob_gas = if spec_id.is_enabled_in(SpecId::PRAGUE) {
+        TARGET_BLOB_GAS_PER_BLOCK_PRAGUE
+    } else {
+        TARGET_BLOB_GAS_PER_BLOCK
+    };
+    (parent_excess_blob_gas + parent_blob_gas_used).saturating_sub(target_blob_gas)
 }
 
 /// Calculates the blob gas price from the header's excess blob gas field.
 ///
+/// Prague uses the update fraction of [EIP-7691](https://eips.ethereum.org/EIPS/eip-7691),
+/// earlier specs the one of EIP-4844.
+///
 /// See also [the EIP-4844 helpers](https://eips.ethereum.org/EIPS/eip-4844#helpers)
 /// (`get_blob_gasprice`).
 #[inline]
-pub fn calc_blob_gasprice(excess_blob_gas: u64) -> u128 {
-    fake_exponential(
-        MIN_BLOB_GASPRICE,
-        excess_blob_gas,
-        BLOB_GASPRICE_UPDATE_FRACTION,
-    )
+pub fn calc_blob_gasprice(excess_blob_gas: u64, spec_id: SpecId) -> u128 {
+    let update_fraction = if spec_id.is_enabled_in(SpecId::PRAGUE) {
+        BLOB_GASPRICE_UPDATE_FRACTION_PRAGUE
+    } else {
+        BLOB_GASPRICE_UPDATE_FRACTION
+    };
+    fake_exponential(MIN_BLOB_GASPRICE, excess_blob_gas, update_fraction)
 }
 
 /// Approximates `factor * e ** (numerator / denominator)` using Taylor expansion.
@@ -62,7 +94,7 @@ pub fn fake_exponential(factor: u64, numerator: u64, denominator: u64) -> u128 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::GAS_PER_BLOB;
+    use crate::{address, alloy_primitives::bloom, Bytes, LogData, GAS_PER_BLOB};
 
     // https://github.com/ethereum/go-ethereum/blob/28857080d732857030eda80c69b9ba2c8926f221/consensus/misc/eip4844/eip4844_test.go#L27
     #[test]
@@ -113,11 +145,30 @@ mod tests {
                 0,
             ),
         ] {
-            let actual = calc_excess_blob_gas(excess, blobs * GAS_PER_BLOB);
+            let actual = calc_excess_blob_gas(excess, blobs * GAS_PER_BLOB, SpecId::CANCUN);
             assert_eq!(actual, expected, "test: {t:?}");
         }
     }
 
+    #[test]
+    fn test_calc_excess_blob_gas_prague() {
+        // a Cancun target is exceeded, the Prague target is not.
+        let blob_gas_used = TARGET_BLOB_GAS_PER_BLOCK + GAS_PER_BLOB;
+        assert_eq!(
+            calc_excess_blob_gas(0, blob_gas_used, SpecId::CANCUN),
+            GAS_PER_BLOB
+        );
+        assert_eq!(calc_excess_blob_gas(0, blob_gas_used, SpecId::PRAGUE), 0);
+        assert_eq!(
+            calc_excess_blob_gas(
+                0,
+                TARGET_BLOB_GAS_PER_BLOCK_PRAGUE + GAS_PER_BLOB,
+                SpecId::PRAGUE
+            ),
+            GAS_PER_BLOB
+        );
+    }
+
     // https://github.com/ethereum/go-ethereum/blob/28857080d732857030eda80c69b9ba2c8926f221/consensus/misc/eip4844/eip4844_test.go#L60
     #[test]
     fn test_calc_blob_fee() {
@@ -137,11 +188,19 @@ mod tests {
         ];
 
         for &(excess, expected) in blob_fee_vectors {
-            let actual = calc_blob_gasprice(excess);
+            let actual = calc_blob_gasprice(excess, SpecId::CANCUN);
             assert_eq!(actual, expected, "test: {excess}");
         }
     }
 
+    #[test]
+    fn blob_fee_update_fraction_changes_in_prague() {
+        let excess = 10 * 1024 * 1024;
+        assert_eq!(calc_blob_gasprice(excess, SpecId::CANCUN), 23);
+        assert_eq!(calc_blob_gasprice(excess, SpecId::PRAGUE), 8);
+        assert_eq!(calc_blob_gasprice(excess, SpecId::PRAGUE_EOF), 8);
+    }
+
     // https://github.com/ethereum/go-ethereum/blob/28857080d732857030eda80c69b9ba2c8926f221/consensus/misc/eip4844/eip4844_test.go#L78
     #[test]
     fn fake_exp() {
@@ -167,4 +226,39 @@ mod tests {
             assert_eq!(actual, expected, "test: {t:?}");
         }
     }
+
+    #[test]
+    fn test_logs_bloom() {
+        let log = Log {
+            address: address!("ef2d6d194084c2de36e0dabfce45d046b37d1106"),
+            data: LogData::new_unchecked(
+                vec![b256!(
+                    "02c69be41d0b7e40352fc85be1cd65eb03d40ef8427a0ca4596b1ead9a00e9fc"
+                )],
+                Bytes::new(),
+            ),
+        };
+        let expected = bloom!(
+            "00000000000000000000000000000000
+             00000000100000000000000000000000
+             00000000000000000000000000000000
+             00000000000000000000000000000000
+             00000000000000000000000000000000
+             00000000000000000000000000000000
+             00000002020000000000000000000000
+             00000000000000000000000800000000
+             10000000000000000000000000000000
+             00000000000000000000001000000000
+             00000000000000000000000000000000
+             00000000000000000000000000000000
+             00000000000000000000000000000000
+             00000000000000000000000000000000
+             00000000000000000000000000000000
+             00000000000000000000000000000000"
+        );
+        assert_eq!(logs_bloom([&log]), expected);
+        // the same log in another receipt does not change the bloom.
+        assert_eq!(logs_bloom([&log, &log]), expected);
+        assert_eq!(logs_bloom([]), Bloom::ZERO);
+    }
 }
@@ -4,14 +4,22 @@ use criterion::{
 use revm::{
     db::BenchmarkDB,
     interpreter::{analysis::to_analysed, Contract, DummyHost, Interpreter},
-    primitives::{address, bytes, hex, BerlinSpec, Bytecode, Bytes, EthereumWiring, TxKind, U256},
+    primitives::{
+        address, bytes, hex, keccak256, BerlinSpec, Bytecode, Bytes, EnvKeccak, EthereumWiring,
+        KeccakBackend, TxKind, B256, U256,
+    },
     Evm,
 };
-use revm_interpreter::{opcode::make_instruction_table, SharedMemory, EMPTY_SHARED_MEMORY};
-use std::time::Duration;
+use revm_interpreter::{
+    opcode::{make_instruction_table, *},
+    SharedMemory, EMPTY_SHARED_MEMORY,
+};
+use std::{sync::Arc, time::Duration};
 
 fn analysis(c: &mut Criterion) {
     let evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+        .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+        .with_default_ext_ctx()
         .modify_tx_env(|tx| {
             tx.caller = address!("0000000000000000000000000000000000000002");
             tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
@@ -45,6 +53,7 @@ fn analysis(c: &mut Criterion) {
 fn snailtracer(c: &mut Criterion) {
     let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
         .with_db(BenchmarkDB::new_bytecode(bytecode(SNAILTRACER)))
+        .with_default_ext_ctx()
         .modify_tx_env(|tx| {
             tx.caller = address!("1000000000000000000000000000000000000000");
             tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
@@ -65,6 +74,7 @@ fn snailtracer(c: &mut Criterion) {
 fn transfer(c: &mut Criterion) {
     let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
         .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+        .with_default_ext_ctx()
         .modify_tx_env(|tx| {
             tx.caller = address!("0000000000000000000000000000000000000001");
             tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
@@ -78,6 +88,41 @@ fn transfer(c: &mut Criterion) {
     g.finish();
 }
 
+/// Forwards to the builtin implementation, to measure the cost of [`EnvKeccak::Custom`].
+#[derive(Debug)]
+struct ForwardingKeccak;
+
+impl KeccakBackend for ForwardingKeccak {
+    fn keccak256(&self, input: &[u8]) -> B256 {
+        keccak256(input)
+    }
+}
+
+fn keccak(c: &mut Criterion) {
+    // hashes 32 bytes of memory 0x1000 times.
+    let code = [
+        PUSH2, 0x10, 0x00, JUMPDEST, PUSH1, 0x20, PUSH1, 0x00, KECCAK256, POP, PUSH1, 0x01, SWAP1,
+        SUB, DUP1, PUSH1, 0x03, JUMPI, STOP,
+    ];
+    let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+        .with_db(BenchmarkDB::new_bytecode(to_analysed(Bytecode::new_raw(
+            code.into(),
+        ))))
+        .with_default_ext_ctx()
+        .modify_tx_env(|tx| {
+            tx.caller = address!("1000000000000000000000000000000000000000");
+            tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+        })
+        .build();
+
+    let mut g = c.benchmark_group("keccak");
+    g.noise_threshold(0.03).warm_up_time(Duration::from_secs(1));
+    g.bench_function("transact/default", |b| b.iter(|| evm.transact().unwrap()));
+    evm.cfg_mut().keccak = EnvKeccak::Custom(Arc::new(ForwardingKeccak));
+    g.bench_function("transact/custom", |b| b.iter(|| evm.transact().unwrap()));
+    g.finish();
+}
+
 fn bench_transact(
     g: &mut BenchmarkGroup<'_, WallTime>,
     evm: &mut Evm<'_, EthereumWiring<BenchmarkDB, ()>>,
@@ -129,6 +174,7 @@ criterion_group!(
     analysis,
     snailtracer,
     transfer,
+    keccak,
 );
 criterion_main!(benches);
 
@@ -1,7 +1,10 @@
 mod context_precompiles;
 pub(crate) mod evm_context;
 mod inner_evm_context;
+mod state_override;
 
+#[cfg(feature = "std")]
+pub use context_precompiles::PrecompileMetricsHook;
 pub use context_precompiles::{
     ContextPrecompile, ContextPrecompiles, ContextStatefulPrecompile, ContextStatefulPrecompileArc,
     ContextStatefulPrecompileBox, ContextStatefulPrecompileMut,
@@ -10,6 +13,7 @@ use derive_where::derive_where;
 pub use evm_context::EvmContext;
 pub use inner_evm_context::InnerEvmContext;
 use revm_interpreter::{as_u64_saturated, Eip7702CodeLoad, StateLoad};
+pub use state_override::{AccountOverride, StateOverride};
 
 use crate::{
     db::{Database, EmptyDB},
@@ -170,8 +174,18 @@ impl<EvmWiringT: EvmWiring> Host for Context<EvmWiringT> {
         self.evm.tstore(address, index, value)
     }
 
-    fn log(&mut self, log: Log) {
+    fn log(&mut self, log: Log) -> bool {
+        if let Some(max_logs) = self.env().cfg.max_logs {
+            if self.log_count() >= max_logs {
+                return false;
+            }
+        }
         self.evm.journaled_state.log(log);
+        true
+    }
+
+    fn log_count(&self) -> u64 {
+        self.evm.journaled_state.logs.len() as u64
     }
 
     fn selfdestruct(
@@ -187,3 +201,17 @@ impl<EvmWiringT: EvmWiring> Host for Context<EvmWiringT> {
             .ok()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_logs_is_enforced_by_host_log() {
+        let mut context = Context::<EthereumWiring<EmptyDB, ()>>::default();
+        context.env_mut().cfg.max_logs = Some(1);
+        assert!(context.log(Log::default()));
+        assert!(!context.log(Log::default()));
+        assert_eq!(context.log_count(), 1);
+    }
+}
@@ -39,11 +39,20 @@ enum PrecompilesCow<EvmWiringT: EvmWiring> {
     Owned(HashMap<Address, ContextPrecompile<EvmWiringT>>),
 }
 
+/// Callback invoked after each precompile call with the precompile address and the time the
+/// call took.
+#[cfg(feature = "std")]
+pub type PrecompileMetricsHook = Arc<dyn Fn(&Address, std::time::Duration) + Send + Sync>;
+
 /// Precompiles context.
 
 #[derive_where(Clone, Debug, Default)]
 pub struct ContextPrecompiles<EvmWiringT: EvmWiring> {
     inner: PrecompilesCow<EvmWiringT>,
+    /// Optional timing hook. When `None`, precompile calls are not timed.
+    #[cfg(feature = "std")]
+    #[derive_where(skip(Debug))]
+    metrics_hook: Option<PrecompileMetricsHook>,
 }
 
 impl<EvmWiringT: EvmWiring> ContextPrecompiles<EvmWiringT> {
@@ -63,6 +72,8 @@ impl<EvmWiringT: EvmWiring> ContextPrecompiles<EvmWiringT> {
     pub fn from_static_precompiles(precompiles: &'static Precompiles) -> Self {
         Self {
             inner: PrecompilesCow::StaticRef(precompiles),
+            #[cfg(feature = "std")]
+            metrics_hook: None,
         }
     }
 
@@ -71,6 +82,8 @@ impl<EvmWiringT: EvmWiring> ContextPrecompiles<EvmWiringT> {
     pub fn from_precompiles(precompiles: HashMap<Address, ContextPrecompile<EvmWiringT>>) -> Self {
         Self {
             inner: PrecompilesCow::Owned(precompiles),
+            #[cfg(feature = "std")]
+            metrics_hook: None,
         }
     }
 
@@ -111,7 +124,13 @@ impl<EvmWiringT: EvmWiring> ContextPrecompiles<EvmWiringT> {
         gas_limit: u64,
         evmctx: &mut InnerEvmContext<EvmWiringT>,
     ) -> Option<PrecompileResult> {
-        Some(match self.inner {
+        #[cfg(feature = "std")]
+        let start = self
+            .metrics_hook
+            .as_ref()
+            .map(|_| std::time::Instant::now());
+
+        let result = match self.inner {
             PrecompilesCow::StaticRef(p) => {
                 p.get(address)?.call_ref(bytes, gas_limit, &evmctx.env.cfg)
             }
@@ -120,7 +139,21 @@ impl<EvmWiringT: EvmWiring> ContextPrecompiles<EvmWiringT> {
                 ContextPrecompile::ContextStateful(p) => p.call(bytes, gas_limit, evmctx),
                 ContextPrecompile::ContextStatefulMut(p) => p.call_mut(bytes, gas_limit, evmctx),
             },
-        })
+        };
+
+        #[cfg(feature = "std")]
+        if let (Some(hook), Some(start)) = (&self.metrics_hook, start) {
+            hook(address, start.elapsed());
+        }
+
+        Some(result)
+    }
+
+    /// Sets the hook that is called after each precompile call with its address and elapsed time.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn set_metrics_hook(&mut self, hook: Option<PrecompileMetricsHook>) {
+        self.metrics_hook = hook;
     }
 
     /// Returns a mutable reference to the precompiles map.
@@ -138,6 +171,18 @@ impl<EvmWiringT: EvmWiring> ContextPrecompiles<EvmWiringT> {
         inner
     }
 
+    /// Disables the precompile at `address`, returning it if it was present.
+    ///
+    /// A disabled precompile address behaves as a normal account, a call to it executes the
+    /// code of the account, which is usually empty.
+    #[inline]
+    pub fn disable(&mut self, address: &Address) -> Option<ContextPrecompile<EvmWiringT>> {
+        if !self.contains(address) {
+            return None;
+        }
+        self.to_mut().remove(address)
+    }
+
     /// Mutates Self into Owned variant, or do nothing if it is already Owned.
     /// Mutation will clone all precompiles.
     #[cold]
@@ -183,6 +228,9 @@ impl<EvmWiringT: EvmWiring> Default for PrecompilesCow<EvmWiringT> {
 
 /// Context aware stateful precompile trait. It is used to create
 /// a arc precompile in [`ContextPrecompile`].
+///
+/// State changes made through the journaled state of `evmctx` are part of the call's
+/// checkpoint: they are reverted if the precompile fails or an enclosing call reverts.
 pub trait ContextStatefulPrecompile<EvmWiringT: EvmWiring>: Sync + Send {
     fn call(
         &self,
@@ -194,6 +242,8 @@ pub trait ContextStatefulPrecompile<EvmWiringT: EvmWiring>: Sync + Send {
 
 /// Context aware mutable stateful precompile trait. It is used to create
 /// a boxed precompile in [`ContextPrecompile`].
+///
+/// State changes are reverted like the ones of [`ContextStatefulPrecompile`].
 pub trait ContextStatefulPrecompileMut<EvmWiringT: EvmWiring>: DynClone + Send + Sync {
     fn call_mut(
         &mut self,
@@ -221,7 +271,7 @@ impl<EvmWiringT: EvmWiring> From<Precompile> for ContextPrecompile<EvmWiringT> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::primitives::DefaultEthereumWiring;
+    use crate::{db::EmptyDB, primitives::DefaultEthereumWiring};
 
     #[test]
     fn test_precompiles_context() {
@@ -238,5 +288,152 @@ mod tests {
         assert_eq!(precompiles.addresses().count(), 5);
         assert!(matches!(precompiles.inner, PrecompilesCow::Owned(_)));
         assert!(precompiles.contains(&custom_address));
+
+        assert!(precompiles.disable(&custom_address).is_some());
+        assert!(precompiles.disable(&custom_address).is_none());
+        assert_eq!(precompiles.addresses().count(), 4);
+        assert!(!precompiles.contains(&custom_address));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_precompiles_metrics_hook() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        use revm_precompile::u64_to_address;
+
+        let identity = u64_to_address(4);
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let mut precompiles =
+            ContextPrecompiles::<DefaultEthereumWiring>::new(PrecompileSpecId::HOMESTEAD);
+        let counter = calls.clone();
+        precompiles.set_metrics_hook(Some(Arc::new(move |address, _elapsed| {
+            assert_eq!(*address, identity);
+            counter.fetch_add(1, Ordering::Relaxed);
+        })));
+
+        let mut context = InnerEvmContext::<DefaultEthereumWiring>::new(EmptyDB::default());
+        for _ in 0..2 {
+            let result = precompiles.call(&identity, &Bytes::new(), 1_000, &mut context);
+            assert!(matches!(result, Some(Ok(_))));
+        }
+        // Missing precompiles are not timed.
+        assert!(precompiles
+            .call(
+                &Address::with_last_byte(0xff),
+                &Bytes::new(),
+                1_000,
+                &mut context
+            )
+            .is_none());
+
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn stateful_precompile_writes_revert_with_caller() {
+        use crate::{
+            db::{CacheDB, EmptyDB},
+            interpreter::opcode::*,
+            primitives::{
+                address, AccountInfo, Bytecode, EthereumWiring, PrecompileOutput, SpecId, U256,
+            },
+            test_utils::evm_with_code,
+        };
+
+        type TestWiring = EthereumWiring<CacheDB<EmptyDB>, ()>;
+        const PRECOMPILE: Address = Address::with_last_byte(0xff);
+
+        /// Stores 1 in slot 1 of its own account.
+        struct StorePrecompile;
+
+        impl ContextStatefulPrecompile<TestWiring> for StorePrecompile {
+            fn call(
+                &self,
+                _bytes: &Bytes,
+                _gas_limit: u64,
+                evmctx: &mut InnerEvmContext<TestWiring>,
+            ) -> PrecompileResult {
+                evmctx
+                    .journaled_state
+                    .sstore(PRECOMPILE, U256::from(1), U256::from(1), &mut evmctx.db)
+                    .unwrap();
+                Ok(PrecompileOutput::new(0, Bytes::new()))
+            }
+        }
+
+        let b = address!("0000000000000000000000000000000000002000");
+        // CALL(gas, b, 0, 0, 0, 0, 0)
+        let mut code = vec![PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH20];
+        code.extend_from_slice(b.as_slice());
+        code.extend_from_slice(&[GAS, CALL, STOP]);
+
+        for (end, expected) in [(STOP, U256::from(1)), (REVERT, U256::ZERO)] {
+            // CALL(gas, PRECOMPILE, 0, 0, 0, 0, 0) followed by STOP or REVERT(0, 0)
+            let mut code_b = vec![PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH20];
+            code_b.extend_from_slice(PRECOMPILE.as_slice());
+            code_b.extend_from_slice(&[GAS, CALL, PUSH1, 0, PUSH1, 0, end]);
+
+            let mut evm = evm_with_code(&code, SpecId::LATEST);
+            evm.db_mut().insert_account_info(
+                b,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(code_b.into())),
+            );
+            let precompiles = evm.handler.pre_execution.load_precompiles();
+            evm.handler.pre_execution.load_precompiles = Arc::new(move || {
+                let mut precompiles = precompiles.clone();
+                precompiles.extend([(
+                    PRECOMPILE,
+                    ContextPrecompile::ContextStateful(Arc::new(StorePrecompile)),
+                )]);
+                precompiles
+            });
+            let result = evm.transact().unwrap();
+            assert!(result.result.is_success());
+
+            let slot = &result.state[&PRECOMPILE].storage[&U256::from(1)];
+            assert_eq!(slot.present_value(), expected);
+        }
+    }
+
+    #[test]
+    fn disabled_precompile_behaves_as_empty_account() {
+        use crate::{
+            primitives::{hex, ExecutionResult, SpecId, SuccessReason, TxKind},
+            test_utils::evm_with_code,
+        };
+
+        // hash, v, r and s of a valid signature.
+        let input = hex!("456e9aea5e197a1f1af7a3e85a3212fa4049a3ba34c2289b4c860fc0b0c64ef3000000000000000000000000000000000000000000000000000000000000001c9242685bf161793cc25603c231bc2f568eb630ea16aa137d2664ac80388256084f8ae3bd7535248d0bd448298cc2e2071e56992d0774dc340c368ae950852ada");
+        let ecrecover = Address::with_last_byte(1);
+
+        let transact = |disable: bool| {
+            let mut evm = evm_with_code(&[], SpecId::LATEST);
+            evm.tx_mut().transact_to = TxKind::Call(ecrecover);
+            evm.tx_mut().data = Bytes::copy_from_slice(&input);
+            if disable {
+                let precompiles = evm.handler.pre_execution.load_precompiles();
+                evm.handler.pre_execution.load_precompiles = Arc::new(move || {
+                    let mut precompiles = precompiles.clone();
+                    precompiles.disable(&ecrecover);
+                    precompiles
+                });
+            }
+            evm.transact().unwrap().result
+        };
+
+        let recovered = transact(false);
+        assert!(recovered.is_success());
+        assert_eq!(recovered.output().unwrap().len(), 32);
+
+        let disabled = transact(true);
+        assert!(matches!(
+            disabled,
+            ExecutionResult::Success {
+                reason: SuccessReason::Stop,
+                ..
+            }
+        ));
+        assert!(disabled.output().unwrap().is_empty());
     }
 }
@@ -2,16 +2,16 @@ use derive_where::derive_where;
 use revm_interpreter::CallValue;
 use revm_precompile::PrecompileErrors;
 
-use super::inner_evm_context::InnerEvmContext;
+use super::{inner_evm_context::InnerEvmContext, StateOverride};
 use crate::{
     db::Database,
     interpreter::{
         analysis::validate_eof, return_ok, CallInputs, Contract, CreateInputs, EOFCreateInputs,
-        EOFCreateKind, Gas, InstructionResult, Interpreter, InterpreterResult,
+        EOFCreateKind, Gas, InstructionResult, Interpreter, InterpreterResult, Stack,
     },
     primitives::{
-        keccak256, Address, Bytecode, Bytes, CreateScheme, EVMError, EVMResultGeneric, EnvWiring,
-        Eof,
+        keccak256, AccountStatus, Address, Bytecode, Bytes, CreateScheme, EVMError,
+        EVMResultGeneric, EnvWiring, Eof, EvmStorageSlot,
         SpecId::{self, *},
         Transaction, B256, EOF_MAGIC_BYTES,
     },
@@ -95,6 +95,45 @@ where
         self.precompiles = precompiles;
     }
 
+    /// Applies state overrides for the next transaction.
+    ///
+    /// Overridden accounts and storage slots are loaded into the journaled state, shadowing
+    /// the database values, and are dropped together with it once the transaction is
+    /// finalized. The database is never written to, unless the resulting state is committed.
+    ///
+    /// Must be called before the transaction is executed.
+    pub fn with_state_override(
+        &mut self,
+        overrides: StateOverride,
+    ) -> Result<(), <EvmWiringT::Database as Database>::Error> {
+        for (address, account_override) in overrides {
+            let account =
+                self.inner
+                    .journaled_state
+                    .initial_account_load(address, [], &mut self.inner.db)?;
+            // overrides are not an access, account and slots stay cold.
+            account.mark_cold();
+            account.status -= AccountStatus::LoadedAsNotExisting;
+
+            if let Some(balance) = account_override.balance {
+                account.info.balance = balance;
+            }
+            if let Some(nonce) = account_override.nonce {
+                account.info.nonce = nonce;
+            }
+            if let Some(code) = account_override.code {
+                account.info.code_hash = code.hash_slow();
+                account.info.code = Some(code);
+            }
+            for (key, value) in account_override.storage {
+                let mut slot = EvmStorageSlot::new(value);
+                slot.mark_cold();
+                account.storage.insert(key, slot);
+            }
+        }
+        Ok(())
+    }
+
     /// Call precompile contract
     #[inline]
     fn call_precompile(
@@ -137,6 +176,18 @@ where
         Ok(Some(result))
     }
 
+    /// Creates the interpreter of a new frame, with the stack limit of [`CfgEnv`].
+    ///
+    /// [`CfgEnv`]: crate::primitives::CfgEnv
+    #[inline]
+    fn new_interpreter(&self, contract: Contract, gas_limit: u64, is_static: bool) -> Interpreter {
+        let stack = match self.env.cfg.limit_stack_size {
+            Some(limit) => Stack::with_limit(limit),
+            None => Stack::new(),
+        };
+        Interpreter::new_with_stack(contract, gas_limit, is_static, stack)
+    }
+
     /// Make call frame
     #[inline]
     pub fn make_call_frame(
@@ -250,7 +301,7 @@ where
             Ok(FrameOrResult::new_call_frame(
                 inputs.return_memory_offset.clone(),
                 checkpoint,
-                Interpreter::new(contract, gas.limit(), inputs.is_static),
+                self.new_interpreter(contract, gas.limit(), inputs.is_static),
             ))
         }
     }
@@ -345,7 +396,7 @@ where
         Ok(FrameOrResult::new_create_frame(
             created_address,
             checkpoint,
-            Interpreter::new(contract, inputs.gas_limit, false),
+            self.new_interpreter(contract, inputs.gas_limit, false),
         ))
     }
 
@@ -448,7 +499,7 @@ where
             inputs.value,
         );
 
-        let mut interpreter = Interpreter::new(contract, inputs.gas_limit, false);
+        let mut interpreter = self.new_interpreter(contract, inputs.gas_limit, false);
         // EOF init will enable RETURNCONTRACT opcode.
         interpreter.set_is_eof_init();
 
@@ -553,7 +604,11 @@ mod tests {
     use crate::primitives::U256;
     use crate::{
         db::{CacheDB, EmptyDB},
-        primitives::{address, Bytecode, DefaultEthereumWiring, EthereumWiring},
+        interpreter::gas,
+        primitives::{
+            address, AccessList, AccessListItem, Bytecode, CreateScheme, DefaultEthereumWiring,
+            EthereumWiring,
+        },
         Frame, JournalEntry,
     };
     use std::boxed::Box;
@@ -648,4 +703,286 @@ mod tests {
         };
         assert_eq!(call_frame.return_memory_range, 0..0,);
     }
+
+    #[test]
+    fn test_preview_create() {
+        type CacheEthWiring = EthereumWiring<CacheDB<EmptyDB>, ()>;
+        let env = EnvWiring::<CacheEthWiring>::default();
+        let mut cdb = CacheDB::new(EmptyDB::default());
+        cdb.insert_account_info(
+            MOCK_CALLER,
+            crate::primitives::AccountInfo {
+                nonce: 5,
+                ..Default::default()
+            },
+        );
+        let mut context = create_cache_db_evm_context::<CacheEthWiring>(Box::new(env), cdb);
+
+        // 1000 words of initcode.
+        let init_code = Bytes::from(vec![0u8; 32_000]);
+        let words = 1000;
+
+        let (address, gas_cost) = context
+            .preview_create(
+                CreateScheme::Create,
+                MOCK_CALLER,
+                &init_code,
+                SpecId::CANCUN,
+            )
+            .unwrap();
+        assert_eq!(address, MOCK_CALLER.create(5));
+        assert_eq!(gas_cost, gas::CREATE + gas::INITCODE_WORD_COST * words);
+
+        let salt = U256::from(7);
+        let (address, gas_cost) = context
+            .preview_create(
+                CreateScheme::Create2 { salt },
+                MOCK_CALLER,
+                &init_code,
+                SpecId::CANCUN,
+            )
+            .unwrap();
+        assert_eq!(
+            address,
+            MOCK_CALLER.create2_from_code(salt.to_be_bytes(), &init_code)
+        );
+        assert_eq!(
+            gas_cost,
+            gas::CREATE + (gas::KECCAK256WORD + gas::INITCODE_WORD_COST) * words
+        );
+
+        // No initcode word cost before Shanghai.
+        let (_, gas_cost) = context
+            .preview_create(CreateScheme::Create, MOCK_CALLER, &init_code, SpecId::MERGE)
+            .unwrap();
+        assert_eq!(gas_cost, gas::CREATE);
+
+        // Previewing does not load the caller into the journaled state.
+        assert!(context.journaled_state.state.is_empty());
+    }
+
+    #[test]
+    fn test_apply_access_list() {
+        type CacheEthWiring = EthereumWiring<CacheDB<EmptyDB>, ()>;
+        let env = EnvWiring::<CacheEthWiring>::default();
+        let mut context = create_cache_db_evm_context::<CacheEthWiring>(
+            Box::new(env),
+            CacheDB::new(EmptyDB::default()),
+        );
+
+        let address = address!("0000000000000000000000000000000000001000");
+        let list = AccessList(vec![AccessListItem {
+            address,
+            storage_keys: vec![B256::with_last_byte(1), B256::with_last_byte(2)],
+        }]);
+        let gas_cost = context.apply_access_list(&list).unwrap();
+        assert_eq!(
+            gas_cost,
+            gas::ACCESS_LIST_ADDRESS + 2 * gas::ACCESS_LIST_STORAGE_KEY
+        );
+
+        let db = &mut context.inner.db;
+        let journaled_state = &mut context.inner.journaled_state;
+        assert!(!journaled_state.load_account(address, db).unwrap().is_cold);
+        for key in [1, 2] {
+            assert!(
+                !journaled_state
+                    .sload(address, U256::from(key), db)
+                    .unwrap()
+                    .is_cold
+            );
+        }
+        assert!(
+            journaled_state
+                .sload(address, U256::from(3), db)
+                .unwrap()
+                .is_cold
+        );
+    }
+    #[test]
+    fn test_apply_authorization_list() {
+        use crate::primitives::{
+            eip7702, AccountInfo, Authorization, RecoveredAuthorization, Signature,
+        };
+
+        type CacheEthWiring = EthereumWiring<CacheDB<EmptyDB>, ()>;
+        let existing = address!("0000000000000000000000000000000000001000");
+        let new = address!("0000000000000000000000000000000000002000");
+        let wrong_chain = address!("0000000000000000000000000000000000003000");
+        let wrong_nonce = address!("0000000000000000000000000000000000004000");
+        let [a, b] = [
+            address!("000000000000000000000000000000000000a000"),
+            address!("000000000000000000000000000000000000b000"),
+        ];
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(existing, AccountInfo::from_balance(U256::from(1)));
+        db.insert_account_info(
+            wrong_nonce,
+            AccountInfo {
+                nonce: 1,
+                ..AccountInfo::from_balance(U256::from(1))
+            },
+        );
+        let env = EnvWiring::<CacheEthWiring>::default();
+        let mut context = create_cache_db_evm_context::<CacheEthWiring>(Box::new(env), db);
+
+        let signed = |chain_id: u64, address, nonce| {
+            Authorization {
+                chain_id: U256::from(chain_id),
+                address,
+                nonce,
+            }
+            .into_signed(Signature::test_signature())
+        };
+        let auth = |authority, chain_id, address, nonce| {
+            RecoveredAuthorization::new_unchecked(signed(chain_id, address, nonce), Some(authority))
+        };
+        let list = [
+            auth(existing, 1, a, 0),
+            // the same authority again, with the incremented nonce and any chain id.
+            auth(existing, 0, b, 1),
+            auth(new, 1, a, 0),
+            auth(wrong_chain, 2, a, 0),
+            auth(wrong_nonce, 1, a, 0),
+            // the signature could not be recovered.
+            RecoveredAuthorization::new_unchecked(signed(1, a, 0), None),
+        ];
+        let gas = context.apply_authorization_list(&list).unwrap();
+        // both authorizations of `existing` are refunded.
+        assert_eq!(
+            gas,
+            6 * eip7702::PER_EMPTY_ACCOUNT_COST
+                - 2 * (eip7702::PER_EMPTY_ACCOUNT_COST - eip7702::PER_AUTH_BASE_COST)
+        );
+
+        let state = &context.journaled_state.state;
+        for (authority, delegate, nonce) in [(existing, b, 2), (new, a, 1)] {
+            let info = &state[&authority].info;
+            assert_eq!(info.code, Some(Bytecode::new_eip7702(delegate)));
+            assert_eq!(info.nonce, nonce);
+        }
+        assert!(!state.contains_key(&wrong_chain));
+        let info = &state[&wrong_nonce].info;
+        assert!(info.code.iter().all(Bytecode::is_empty));
+        assert_eq!(info.nonce, 1);
+    }
+
+    #[test]
+    fn short_input_to_fixed_length_precompile_fails() {
+        use crate::{
+            interpreter::opcode::*,
+            primitives::{ExecutionResult, HaltReason, TxKind},
+            test_utils::{evm_with_code, CONTRACT},
+        };
+
+        let blake2 = address!("0000000000000000000000000000000000000009");
+        // SSTORE(0, ISZERO(CALL(10_000, blake2, 0, 0, 100, 0, 0)))
+        let code = [
+            PUSH1, 0, PUSH1, 0, PUSH1, 100, PUSH1, 0, PUSH1, 0, PUSH1, 9, PUSH2, 0x27, 0x10, CALL,
+            ISZERO, PUSH1, 0, SSTORE, STOP,
+        ];
+        let mut evm = evm_with_code(&code, SpecId::LATEST);
+        evm.tx_mut().data = vec![0; 100].into();
+        evm.tx_mut().gas_limit = 100_000;
+
+        // blake2 requires exactly 213 bytes of input.
+        evm.tx_mut().transact_to = TxKind::Call(blake2);
+        let out = evm.transact().unwrap();
+        assert_eq!(
+            out.result,
+            ExecutionResult::Halt {
+                reason: HaltReason::PrecompileError,
+                gas_used: 100_000,
+            }
+        );
+
+        evm.tx_mut().transact_to = TxKind::Call(CONTRACT);
+        let out = evm.transact().unwrap();
+        assert!(out.result.is_success());
+        assert_eq!(
+            out.state[&CONTRACT].storage[&U256::ZERO].present_value,
+            U256::from(1)
+        );
+    }
+
+    #[test]
+    fn stack_limit_overflows_at_configured_size() {
+        use crate::{
+            interpreter::opcode::*,
+            primitives::{ExecutionResult, HaltReason},
+            test_utils::evm_with_code,
+        };
+
+        let code = [PUSH1, 1, PUSH1, 2, PUSH1, 3, PUSH1, 4, PUSH1, 5, STOP];
+        let mut evm = evm_with_code(&code, SpecId::LATEST);
+        evm.cfg_mut().limit_stack_size = Some(4);
+        assert!(matches!(
+            evm.transact().unwrap().result,
+            ExecutionResult::Halt {
+                reason: HaltReason::StackOverflow,
+                ..
+            }
+        ));
+
+        // the default limit fits the five words.
+        evm.cfg_mut().limit_stack_size = None;
+        assert!(evm.transact().unwrap().result.is_success());
+    }
+
+    #[test]
+    fn state_override_balance_is_not_written_to_db() {
+        use crate::{
+            interpreter::opcode::*, primitives::AccountInfo, test_utils::evm_with_code,
+            AccountOverride, DatabaseRef,
+        };
+
+        let target = address!("00000000000000000000000000000000000000aa");
+        let mut code = vec![PUSH20];
+        code.extend_from_slice(target.as_slice());
+        code.extend_from_slice(&[BALANCE, PUSH1, 0, MSTORE, PUSH1, 32, PUSH1, 0, RETURN]);
+
+        let mut evm = evm_with_code(&code, SpecId::LATEST);
+        evm.db_mut()
+            .insert_account_info(target, AccountInfo::from_balance(U256::from(1)));
+
+        let result = evm.transact().unwrap().result;
+        assert_eq!(
+            result.output().unwrap()[..],
+            U256::from(1).to_be_bytes::<32>()
+        );
+        let gas_used = result.gas_used();
+
+        evm.context
+            .evm
+            .with_state_override(
+                [(
+                    target,
+                    AccountOverride {
+                        balance: Some(U256::from(1000)),
+                        ..Default::default()
+                    },
+                )]
+                .into_iter()
+                .collect(),
+            )
+            .unwrap();
+        let result = evm.transact().unwrap().result;
+        assert_eq!(
+            result.output().unwrap()[..],
+            U256::from(1000).to_be_bytes::<32>()
+        );
+        // the overridden account is still accessed cold.
+        assert_eq!(result.gas_used(), gas_used);
+
+        assert_eq!(
+            evm.db().basic_ref(target).unwrap().unwrap().balance,
+            U256::from(1)
+        );
+        let result = evm.transact().unwrap().result;
+        assert_eq!(
+            result.output().unwrap()[..],
+            U256::from(1).to_be_bytes::<32>()
+        );
+    }
 }
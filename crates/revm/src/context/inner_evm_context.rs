@@ -8,8 +8,8 @@ use crate::{
     },
     journaled_state::JournaledState,
     primitives::{
-        AccessListItem, Account, Address, AnalysisKind, Bytecode, Bytes, CfgEnv, EnvWiring, Eof,
-        EvmWiring, HashSet, Spec,
+        eip7702, AccessList, AccessListItem, Account, Address, AnalysisKind, Bytecode, Bytes,
+        CfgEnv, CreateScheme, EnvWiring, Eof, EvmWiring, HashSet, RecoveredAuthorization, Spec,
         SpecId::{self, *},
         Transaction, B256, EOF_MAGIC_BYTES, EOF_MAGIC_HASH, U256,
     },
@@ -105,6 +105,105 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
         Ok(())
     }
 
+    /// Warms the accounts and storage slots of the given access list.
+    ///
+    /// Returns the [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930) gas for the list:
+    /// `ACCESS_LIST_ADDRESS` per entry and `ACCESS_LIST_STORAGE_KEY` per storage key,
+    /// duplicates included. The gas is not deducted from anything, the caller is
+    /// responsible for charging it.
+    ///
+    /// Like [`Self::load_access_list`], entries stay warm until the journaled state is
+    /// finalized.
+    pub fn apply_access_list(
+        &mut self,
+        list: &AccessList,
+    ) -> Result<u64, <EvmWiringT::Database as Database>::Error> {
+        let mut gas = 0;
+        for AccessListItem {
+            address,
+            storage_keys,
+        } in list.iter()
+        {
+            self.journaled_state.initial_account_load(
+                *address,
+                storage_keys.iter().map(|i| U256::from_be_bytes(i.0)),
+                &mut self.db,
+            )?;
+            gas +=
+                gas::ACCESS_LIST_ADDRESS + gas::ACCESS_LIST_STORAGE_KEY * storage_keys.len() as u64;
+        }
+        Ok(gas)
+    }
+
+    /// Applies the [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) authorizations of
+    /// `list` in order.
+    ///
+    /// Authorizations with an unrecoverable signature, a chain id other than zero or
+    /// [`CfgEnv::chain_id`], an authority with non-delegated code or a nonce other than the
+    /// authority's nonce are skipped. Each valid authorization sets the delegation designator
+    /// of its authority and increments the authority's nonce, so of several authorizations of
+    /// the same authority with consecutive nonces the latest one takes effect.
+    ///
+    /// Returns the gas for the list: `PER_EMPTY_ACCOUNT_COST` per authorization, less
+    /// `PER_EMPTY_ACCOUNT_COST - PER_AUTH_BASE_COST` for each applied authorization whose
+    /// authority already exists. The gas is not deducted from anything, the caller is
+    /// responsible for charging it.
+    pub fn apply_authorization_list(
+        &mut self,
+        list: &[RecoveredAuthorization],
+    ) -> Result<u64, <EvmWiringT::Database as Database>::Error> {
+        let mut refunded_accounts = 0;
+        for authorization in list {
+            // 1. recover authority and authorized addresses.
+            // authority = ecrecover(keccak(MAGIC || rlp([chain_id, address, nonce])), y_parity, r, s]
+            let Some(authority) = authorization.authority() else {
+                continue;
+            };
+
+            // 2. Verify the chain id is either 0 or the chain's current ID.
+            if !authorization.chain_id().is_zero()
+                && authorization.chain_id() != U256::from(self.env.cfg.chain_id)
+            {
+                continue;
+            }
+
+            // warm authority account and check nonce.
+            // 3. Add authority to accessed_addresses (as defined in EIP-2929.)
+            let mut authority_acc = self.journaled_state.load_code(authority, &mut self.db)?;
+
+            // 4. Verify the code of authority is either empty or already delegated.
+            if let Some(bytecode) = &authority_acc.info.code {
+                // if it is not empty and it is not eip7702
+                if !bytecode.is_empty() && !bytecode.is_eip7702() {
+                    continue;
+                }
+            }
+
+            // 5. Verify the nonce of authority is equal to nonce.
+            if authorization.nonce() != authority_acc.info.nonce {
+                continue;
+            }
+
+            // 6. Refund the sender PER_EMPTY_ACCOUNT_COST - PER_AUTH_BASE_COST gas if authority exists in the trie.
+            if !authority_acc.is_empty() {
+                refunded_accounts += 1;
+            }
+
+            // 7. Set the code of authority to be 0xef0100 || address. This is a delegation designation.
+            let bytecode = Bytecode::new_eip7702(authorization.address);
+            authority_acc.info.code_hash = bytecode.hash_slow();
+            authority_acc.info.code = Some(bytecode);
+
+            // 8. Increase the nonce of authority by one.
+            authority_acc.info.nonce = authority_acc.info.nonce.saturating_add(1);
+            authority_acc.mark_touch();
+        }
+
+        let gas = list.len() as u64 * eip7702::PER_EMPTY_ACCOUNT_COST
+            - refunded_accounts * (eip7702::PER_EMPTY_ACCOUNT_COST - eip7702::PER_AUTH_BASE_COST);
+        Ok(gas)
+    }
+
     /// Return environment.
     #[inline]
     pub fn env(&mut self) -> &mut EnvWiring<EvmWiringT> {
@@ -218,11 +317,23 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
     ///
     /// In case of EOF account it will return `EOF_MAGIC_HASH`
     /// (the hash of `0xEF00`).
+    ///
+    /// Before Prague there are neither EOF nor EIP-7702 accounts, so the code is not loaded.
     #[inline]
     pub fn code_hash(
         &mut self,
         address: Address,
     ) -> Result<Eip7702CodeLoad<B256>, <EvmWiringT::Database as Database>::Error> {
+        if !self.journaled_state.spec.is_enabled_in(PRAGUE) {
+            let acc = self.journaled_state.load_account(address, &mut self.db)?;
+            let hash = if acc.is_empty() {
+                B256::ZERO
+            } else {
+                acc.info.code_hash
+            };
+            return Ok(Eip7702CodeLoad::new_not_delegated(hash, acc.is_cold));
+        }
+
         let acc = self.journaled_state.load_code(address, &mut self.db)?;
         if acc.is_empty() {
             return Ok(Eip7702CodeLoad::new_not_delegated(B256::ZERO, acc.is_cold));
@@ -306,6 +417,45 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
             .selfdestruct(address, target, &mut self.db)
     }
 
+    /// Returns the address that a `CREATE`/`CREATE2` from `caller` would deploy to, and the
+    /// creation gas charged for it: the base `CREATE` cost, the `CREATE2` hashing cost and the
+    /// EIP-3860 initcode word cost.
+    ///
+    /// The caller nonce is read without warming the account, so nothing is journaled.
+    pub fn preview_create(
+        &mut self,
+        scheme: CreateScheme,
+        caller: Address,
+        init_code: &Bytes,
+        spec: SpecId,
+    ) -> Result<(Address, u64), <EvmWiringT::Database as Database>::Error> {
+        let len = init_code.len() as u64;
+        let (address, mut gas_cost) = match scheme {
+            CreateScheme::Create => {
+                let nonce = match self.journaled_state.state.get(&caller) {
+                    Some(account) => account.info.nonce,
+                    None => self
+                        .db
+                        .basic(caller)?
+                        .map(|info| info.nonce)
+                        .unwrap_or_default(),
+                };
+                (caller.create(nonce), gas::CREATE)
+            }
+            CreateScheme::Create2 { salt } => (
+                caller.create2_from_code(salt.to_be_bytes(), init_code),
+                gas::create2_cost(len).unwrap_or(u64::MAX),
+            ),
+        };
+
+        // EIP-3860: Limit and meter initcode
+        if spec.is_enabled_in(SHANGHAI) {
+            gas_cost = gas_cost.saturating_add(gas::initcode_cost(len));
+        }
+
+        Ok((address, gas_cost))
+    }
+
     /// If error is present revert changes, otherwise save EOF bytecode.
     pub fn eofcreate_return<SPEC: Spec>(
         &mut self,
@@ -409,6 +559,7 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
                 interpreter_result.result = InstructionResult::OutOfGas;
                 return;
             } else {
+                // Frontier deploys empty code and keeps the state changes of the init code.
                 interpreter_result.output = Bytes::new();
             }
         }
@@ -429,3 +580,280 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
         interpreter_result.result = InstructionResult::Return;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{CacheDB, EmptyDB},
+        interpreter::opcode::*,
+        primitives::{
+            address, keccak256, AccountInfo, EthereumWiring, ExecutionResult, HaltReason,
+            OutOfGasError, ResultAndState, TxKind, KECCAK_EMPTY, MAX_CODE_SIZE,
+        },
+        test_utils::{db_with_code, evm_with_code, CALLER, CONTRACT},
+        Evm,
+    };
+    use core::convert::Infallible;
+
+    #[test]
+    fn extcodehash_does_not_load_code_before_prague() {
+        /// Database that serves accounts without code and counts code fetches.
+        struct CountingDb {
+            db: CacheDB<EmptyDB>,
+            code_loads: Vec<B256>,
+        }
+
+        impl Database for CountingDb {
+            type Error = Infallible;
+
+            fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+                let info = self.db.basic(address)?;
+                Ok(info.map(AccountInfo::without_code))
+            }
+
+            fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+                self.code_loads.push(code_hash);
+                self.db.code_by_hash(code_hash)
+            }
+
+            fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+                self.db.storage(address, index)
+            }
+
+            fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+                self.db.block_hash(number)
+            }
+        }
+
+        let large = address!("0000000000000000000000000000000000002000");
+        // SSTORE(0, EXTCODEHASH(large))
+        let mut code = vec![PUSH20];
+        code.extend_from_slice(large.as_slice());
+        code.extend_from_slice(&[EXTCODEHASH, PUSH1, 0, SSTORE, STOP]);
+        let large_code = vec![JUMPDEST; MAX_CODE_SIZE];
+        let large_hash = keccak256(&large_code);
+
+        let mut evm = Evm::<EthereumWiring<CountingDb, ()>>::builder()
+            .with_db(CountingDb {
+                db: db_with_code([(CONTRACT, code), (large, large_code)]),
+                code_loads: Vec::new(),
+            })
+            .with_default_ext_ctx()
+            .with_spec_id(SpecId::CANCUN)
+            .modify_tx_env(|tx| {
+                tx.caller = CALLER;
+                tx.transact_to = TxKind::Call(CONTRACT);
+            })
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        assert_eq!(
+            state[&CONTRACT].storage[&U256::ZERO].present_value(),
+            U256::from_be_bytes(large_hash.0)
+        );
+        assert!(!evm.db().code_loads.contains(&large_hash));
+    }
+
+    #[test]
+    fn extcodehash_of_missing_empty_and_contract_accounts() {
+        let missing = address!("0000000000000000000000000000000000002000");
+        let eoa = address!("0000000000000000000000000000000000003000");
+        // SSTORE(i, EXTCODEHASH(address)) for the missing account, the EOA and itself.
+        let mut code = Vec::new();
+        for (i, address) in [missing, eoa, CONTRACT].into_iter().enumerate() {
+            code.push(PUSH20);
+            code.extend_from_slice(address.as_slice());
+            code.extend_from_slice(&[EXTCODEHASH, PUSH1, i as u8, SSTORE]);
+        }
+        code.push(STOP);
+        let code_hash = keccak256(&code);
+
+        let transact = |spec_id: SpecId| {
+            let mut evm = evm_with_code(&code, spec_id);
+            evm.db_mut()
+                .insert_account_info(eoa, AccountInfo::from_balance(U256::from(1)));
+            evm.transact().unwrap()
+        };
+
+        for spec_id in [SpecId::CONSTANTINOPLE, SpecId::CANCUN] {
+            let out = transact(spec_id);
+            assert!(out.result.is_success(), "{spec_id:?}");
+            let slot =
+                |key: u64| B256::from(out.state[&CONTRACT].storage[&U256::from(key)].present_value);
+            assert_eq!(slot(0), B256::ZERO);
+            assert_eq!(slot(1), KECCAK_EMPTY);
+            assert_eq!(slot(2), code_hash);
+        }
+
+        // EIP-1052: EXTCODEHASH is not available before Constantinople.
+        assert!(matches!(
+            transact(SpecId::BYZANTIUM).result,
+            ExecutionResult::Halt {
+                reason: HaltReason::NotActivated,
+                ..
+            }
+        ));
+    }
+
+    /// Returns an EVM running `spec_id` whose transaction deploys `init_code` from [`CALLER`].
+    fn evm_creating(
+        init_code: Vec<u8>,
+        spec_id: SpecId,
+    ) -> Evm<'static, EthereumWiring<CacheDB<EmptyDB>, ()>> {
+        let mut evm = evm_with_code(&[], spec_id);
+        evm.tx_mut().transact_to = TxKind::Create;
+        evm.tx_mut().data = init_code.into();
+        evm
+    }
+
+    /// Runs a create transaction whose init code returns `code_len` bytes of memory with
+    /// `first_byte` at offset 0.
+    fn create_returning(
+        spec_id: SpecId,
+        first_byte: u8,
+        code_len: u16,
+    ) -> ExecutionResult<HaltReason> {
+        let [len_hi, len_lo] = code_len.to_be_bytes();
+        let init_code = vec![
+            PUSH1, first_byte, PUSH1, 0, MSTORE8, PUSH2, len_hi, len_lo, PUSH1, 0, RETURN,
+        ];
+        let mut evm = evm_creating(init_code, spec_id);
+        evm.tx_mut().gas_limit = 10_000_000;
+        evm.transact().unwrap().result
+    }
+
+    #[test]
+    fn create_rejects_code_starting_with_ef_from_london() {
+        assert!(create_returning(SpecId::BERLIN, 0xEF, 1).is_success());
+        assert_eq!(
+            create_returning(SpecId::LONDON, 0xEF, 1),
+            ExecutionResult::Halt {
+                reason: HaltReason::CreateContractStartingWithEF,
+                gas_used: 10_000_000,
+            }
+        );
+    }
+
+    #[test]
+    fn create_rejects_oversized_code() {
+        let max_code_size = MAX_CODE_SIZE as u16;
+        assert!(create_returning(SpecId::LONDON, 0, max_code_size).is_success());
+        assert!(matches!(
+            create_returning(SpecId::LONDON, 0, max_code_size + 1),
+            ExecutionResult::Halt {
+                reason: HaltReason::CreateContractSizeLimit,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn created_address_is_set_only_on_success() {
+        let runtime = [PUSH1, 42, PUSH1, 0, MSTORE, PUSH1, 32, PUSH1, 0, RETURN];
+
+        // CODECOPY(0, 12, len) RETURN(0, len) followed by the runtime code.
+        let mut init_code = vec![
+            PUSH1,
+            runtime.len() as u8,
+            PUSH1,
+            12,
+            PUSH1,
+            0,
+            CODECOPY,
+            PUSH1,
+            runtime.len() as u8,
+            PUSH1,
+            0,
+            RETURN,
+        ];
+        init_code.extend_from_slice(&runtime);
+
+        let mut evm = evm_creating(init_code, SpecId::LATEST);
+        evm.db_mut().insert_account_info(
+            CALLER,
+            AccountInfo {
+                nonce: 3,
+                ..Default::default()
+            },
+        );
+        evm.tx_mut().nonce = 3;
+
+        let result = evm.transact().unwrap().result;
+        assert_eq!(result.created_address(), Some(CALLER.create(3)));
+        assert_eq!(result.output().unwrap().as_ref(), &runtime);
+
+        evm.tx_mut().data = vec![PUSH1, 0, PUSH1, 0, REVERT].into();
+        let result = evm.transact().unwrap().result;
+        assert!(matches!(result, ExecutionResult::Revert { .. }));
+        assert_eq!(result.created_address(), None);
+    }
+
+    #[test]
+    fn created_code_len_matches_code_deposit() {
+        // MLOAD(0) to expand memory for both sizes, RETURN(0, size)
+        let init_code = |size: u8| vec![PUSH1, 0, MLOAD, POP, PUSH1, size, PUSH1, 0, RETURN];
+
+        let mut evm = evm_creating(init_code(32), SpecId::LATEST);
+        let deployed = evm.transact().unwrap();
+        assert_eq!(deployed.created_code_len(), Some(32));
+
+        evm.tx_mut().data = init_code(0).into();
+        let empty = evm.transact().unwrap();
+        assert_eq!(empty.created_code_len(), Some(0));
+        assert_eq!(
+            deployed.gas.execution_gas - empty.gas.execution_gas,
+            32 * gas::CODEDEPOSIT
+        );
+
+        evm.tx_mut().data = vec![PUSH1, 0, PUSH1, 0, REVERT].into();
+        assert_eq!(evm.transact().unwrap().created_code_len(), None);
+    }
+
+    #[test]
+    fn insufficient_code_deposit_gas_is_spec_gated() {
+        let created = CALLER.create(0);
+        // SSTORE(0, 1), RETURN(0, 32)
+        let init_code = vec![PUSH1, 1, PUSH1, 0, SSTORE, PUSH1, 32, PUSH1, 0, RETURN];
+        let deposit = 32 * gas::CODEDEPOSIT;
+
+        let transact = |spec_id: SpecId, gas_limit: u64| {
+            let mut evm = evm_creating(init_code.clone(), spec_id);
+            evm.tx_mut().gas_limit = gas_limit;
+            evm.transact().unwrap()
+        };
+
+        for spec_id in [SpecId::FRONTIER, SpecId::HOMESTEAD] {
+            // leave 100 gas for the code deposit.
+            let gas_used = transact(spec_id, 1_000_000).result.gas_used();
+            let gas_limit = gas_used - deposit + 100;
+            let out = transact(spec_id, gas_limit);
+
+            if spec_id == SpecId::FRONTIER {
+                // the creation succeeds with empty code and keeps its state changes.
+                assert!(out.result.is_success());
+                assert_eq!(out.created_code_len(), Some(0));
+                let account = &out.state[&created];
+                assert!(account.info.is_empty_code_hash());
+                assert_eq!(account.storage[&U256::ZERO].present_value, U256::from(1));
+            } else {
+                // EIP-2: the creation runs out of gas.
+                assert_eq!(
+                    out.result,
+                    ExecutionResult::Halt {
+                        reason: HaltReason::OutOfGas(OutOfGasError::Basic),
+                        gas_used: gas_limit,
+                    }
+                );
+                assert_eq!(out.created_code_len(), None);
+                let mut storage = out
+                    .state
+                    .get(&created)
+                    .into_iter()
+                    .flat_map(|a| a.storage.values());
+                assert!(storage.all(|slot| slot.present_value.is_zero()));
+            }
+        }
+    }
+}
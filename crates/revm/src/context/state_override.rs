@@ -0,0 +1,20 @@
+use crate::primitives::{Address, Bytecode, HashMap, U256};
+
+/// Overrides applied to accounts before executing a transaction.
+///
+/// See [`EvmContext::with_state_override`](crate::EvmContext::with_state_override).
+pub type StateOverride = HashMap<Address, AccountOverride>;
+
+/// Override of a single account. Fields that are `None` keep the database value.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountOverride {
+    /// Account balance.
+    pub balance: Option<U256>,
+    /// Account nonce.
+    pub nonce: Option<u64>,
+    /// Account code. The code hash is updated to match it.
+    pub code: Option<Bytecode>,
+    /// Storage slots that shadow the database values. Other slots are read from the database.
+    pub storage: HashMap<U256, U256>,
+}
@@ -83,6 +83,45 @@ impl<ExtDB> CacheDB<ExtDB> {
     }
 }
 
+/// Portable dump of the cached state of a [CacheDB], see [CacheDB::to_state_dump].
+///
+/// Unlike serializing the [CacheDB] itself, the dump does not contain the underlying database
+/// and the committed logs, so dumps of caches over different databases are interchangeable.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct StateDump {
+    /// Cached accounts with their storage.
+    pub accounts: HashMap<Address, DbAccount>,
+    /// Cached contracts by code hash.
+    pub contracts: HashMap<B256, Bytecode>,
+    /// Cached block hashes by block number.
+    pub block_hashes: HashMap<U256, B256>,
+}
+
+#[cfg(feature = "serde")]
+impl<ExtDB> CacheDB<ExtDB> {
+    /// Returns the cached accounts, storage, contracts and block hashes.
+    pub fn to_state_dump(&self) -> StateDump {
+        StateDump {
+            accounts: self.accounts.clone(),
+            contracts: self.contracts.clone(),
+            block_hashes: self.block_hashes.clone(),
+        }
+    }
+
+    /// Creates a cache holding the state of `dump`, over the default underlying database.
+    pub fn from_state_dump(dump: StateDump) -> Self
+    where
+        ExtDB: Default,
+    {
+        let mut cache = Self::new(ExtDB::default());
+        cache.accounts = dump.accounts;
+        cache.contracts.extend(dump.contracts);
+        cache.block_hashes = dump.block_hashes;
+        cache
+    }
+}
+
 impl<ExtDB: DatabaseRef> CacheDB<ExtDB> {
     /// Returns the account for the given address.
     ///
@@ -414,7 +453,15 @@ impl Database for BenchmarkDB {
 #[cfg(test)]
 mod tests {
     use super::{CacheDB, EmptyDB};
-    use crate::primitives::{db::Database, AccountInfo, Address, U256};
+    use crate::{
+        db::{BlockHashProvider, WithBlockHashProvider},
+        interpreter::opcode,
+        primitives::{
+            db::Database, AccountInfo, Address, Bytecode, EthereumWiring, TxKind, B256, U256,
+        },
+        Evm,
+    };
+    use core::convert::Infallible;
 
     #[test]
     fn test_insert_account_storage() {
@@ -468,6 +515,86 @@ mod tests {
         assert_eq!(new_state.storage(account, key1), Ok(value1));
     }
 
+    #[test]
+    fn test_blockhash_uses_provider() {
+        struct Headers;
+
+        impl BlockHashProvider for Headers {
+            type Error = Infallible;
+
+            fn block_hash(&self, number: u64) -> Result<B256, Self::Error> {
+                Ok(B256::with_last_byte(number as u8))
+            }
+        }
+
+        let contract = Address::with_last_byte(42);
+        let code = Bytecode::new_raw(
+            [
+                opcode::PUSH1,
+                5,
+                opcode::BLOCKHASH,
+                opcode::PUSH1,
+                0,
+                opcode::MSTORE,
+                opcode::PUSH1,
+                32,
+                opcode::PUSH1,
+                0,
+                opcode::RETURN,
+            ]
+            .into(),
+        );
+        let mut db = CacheDB::new(WithBlockHashProvider::new(EmptyDB::default(), Headers));
+        db.insert_account_info(contract, AccountInfo::from_bytecode(code));
+
+        let mut evm = Evm::<EthereumWiring<_, ()>>::builder()
+            .with_db(db)
+            .with_default_ext_ctx()
+            .modify_block_env(|block| block.number = U256::from(10))
+            .modify_tx_env(|tx| tx.transact_to = TxKind::Call(contract))
+            .build();
+
+        let result = evm.transact().unwrap().result;
+        assert_eq!(
+            result.output().unwrap().as_ref(),
+            B256::with_last_byte(5).as_slice()
+        );
+        assert_eq!(
+            evm.db().block_hashes.get(&U256::from(5)),
+            Some(&B256::with_last_byte(5))
+        );
+    }
+
+    #[cfg(feature = "serde-json")]
+    #[test]
+    fn test_state_dump_round_trip() {
+        use crate::DatabaseRef;
+
+        let account = Address::with_last_byte(69);
+        let code = Bytecode::new_raw([opcode::PUSH0, opcode::STOP].into());
+        let mut cache = CacheDB::new(EmptyDB::default());
+        cache.insert_account_info(account, AccountInfo::from_bytecode(code.clone()));
+        cache
+            .insert_account_storage(account, U256::from(1), U256::from(2))
+            .unwrap();
+        cache
+            .block_hashes
+            .insert(U256::from(5), B256::with_last_byte(5));
+
+        let json = serde_json::to_string(&cache.to_state_dump()).unwrap();
+        let loaded = CacheDB::<EmptyDB>::from_state_dump(serde_json::from_str(&json).unwrap());
+
+        let info = loaded.basic_ref(account).unwrap().unwrap();
+        assert_eq!(info, cache.basic_ref(account).unwrap().unwrap());
+        assert_eq!(loaded.code_by_hash_ref(info.code_hash).unwrap(), code);
+        assert_eq!(
+            loaded.storage_ref(account, U256::from(1)).unwrap(),
+            U256::from(2)
+        );
+        assert_eq!(loaded.block_hash_ref(5).unwrap(), B256::with_last_byte(5));
+        assert_eq!(loaded.contracts.len(), cache.contracts.len());
+    }
+
     #[cfg(feature = "serde-json")]
     #[test]
     fn test_serialize_deserialize_cachedb() {
@@ -5,7 +5,8 @@ use super::{
 use crate::db::EmptyDB;
 use revm_interpreter::primitives::{
     db::{Database, DatabaseCommit},
-    hash_map, Account, AccountInfo, Address, Bytecode, HashMap, B256, BLOCK_HASH_HISTORY, U256,
+    hash_map, Account, AccountInfo, Address, Bytecode, HashMap, SpecId, B256, BLOCK_HASH_HISTORY,
+    U256,
 };
 use std::{
     boxed::Box,
@@ -134,6 +135,14 @@ impl<DB: Database> State<DB> {
         self.cache.set_state_clear_flag(has_state_clear);
     }
 
+    /// Sets the state clear flag for blocks of `spec_id`.
+    ///
+    /// Touched empty accounts are removed from Spurious Dragon on and kept before, as needed
+    /// to replay old blocks.
+    pub fn set_state_clear_flag_for_spec(&mut self, spec_id: SpecId) {
+        self.set_state_clear_flag(spec_id.is_enabled_in(SpecId::SPURIOUS_DRAGON));
+    }
+
     pub fn insert_not_existing(&mut self, address: Address) {
         self.cache.insert_not_existing(address)
     }
@@ -309,6 +318,43 @@ mod tests {
     };
     use revm_interpreter::primitives::keccak256;
 
+    #[test]
+    fn touched_empty_account_is_removed_since_spurious_dragon() {
+        use crate::{
+            primitives::{EthereumWiring, TxKind},
+            Evm,
+        };
+
+        let caller = Address::with_last_byte(0xca);
+        let empty = Address::with_last_byte(0xe0);
+
+        let empty_after_zero_value_call = |spec_id| {
+            let mut state = State::builder().build();
+            state.set_state_clear_flag_for_spec(spec_id);
+            state.insert_account(caller, AccountInfo::from_balance(U256::from(1_000_000)));
+            state.insert_account(empty, AccountInfo::default());
+
+            let mut evm = Evm::<EthereumWiring<&mut State<EmptyDB>, ()>>::builder()
+                .with_db(&mut state)
+                .with_default_ext_ctx()
+                .with_spec_id(spec_id)
+                .modify_tx_env(|tx| {
+                    tx.caller = caller;
+                    tx.transact_to = TxKind::Call(empty);
+                })
+                .build();
+            assert!(evm.transact_commit().unwrap().is_success());
+            drop(evm);
+            state.basic(empty).unwrap()
+        };
+
+        assert_eq!(
+            empty_after_zero_value_call(SpecId::TANGERINE),
+            Some(AccountInfo::default())
+        );
+        assert_eq!(empty_after_zero_value_call(SpecId::SPURIOUS_DRAGON), None);
+    }
+
     #[test]
     fn block_hash_cache() {
         let mut state = State::builder().build();
@@ -6,8 +6,9 @@ use crate::{
     handler::Handler,
     interpreter::{CallInputs, CreateInputs, EOFCreateInputs, InterpreterAction, SharedMemory},
     primitives::{
-        CfgEnv, EVMError, EVMResult, EVMResultGeneric, EnvWiring, ExecutionResult, ResultAndState,
-        SpecId, Transaction, TxKind, EOF_MAGIC_BYTES,
+        Block, CfgEnv, EVMError, EVMResult, EVMResultGeneric, EnvWiring, ExecutionResult,
+        HaltReason, OutOfGasError, ResultAndState, SpecId, Transaction, TxEnv, TxKind,
+        EOF_MAGIC_BYTES,
     },
     Context, ContextWithEvmWiring, EvmContext, EvmWiring, Frame, FrameOrResult, FrameResult,
     InnerEvmContext,
@@ -46,12 +47,72 @@ impl<EvmWiringT: EvmWiring<Database: DatabaseCommit>> Evm<'_, EvmWiringT> {
     pub fn transact_commit(
         &mut self,
     ) -> EVMResultGeneric<ExecutionResult<EvmWiringT::HaltReason>, EvmWiringT> {
-        let ResultAndState { result, state } = self.transact()?;
+        let ResultAndState { result, state, .. } = self.transact()?;
         self.context.evm.db.commit(state);
         Ok(result)
     }
 }
 
+impl<EvmWiringT: EvmWiring<Transaction = TxEnv>> Evm<'_, EvmWiringT> {
+    /// Transact transaction and, if it runs out of gas, report the gas it would have needed.
+    ///
+    /// The transaction is executed as in [`Evm::transact`]. If it halts with out of gas, it is
+    /// executed a second time with the gas limit raised to the block gas limit, and the gas
+    /// spent by that run, before refunds, is returned next to the original result. The second
+    /// run does not commit anything and the transaction gas limit is restored afterwards.
+    ///
+    /// Returns `None` if the transaction did not run out of gas, or if it still runs out of
+    /// gas or fails validation with the raised limit, e.g. because the caller cannot pay for it.
+    ///
+    /// Because of the 63/64 rule of EIP-150 the lowest gas limit that succeeds can be higher
+    /// than the reported value when the transaction makes calls.
+    ///
+    /// Note that this executes the transaction twice when it runs out of gas.
+    pub fn transact_dry_run(
+        &mut self,
+    ) -> EVMResultGeneric<(ResultAndState<EvmWiringT::HaltReason>, Option<u64>), EvmWiringT> {
+        let output = self.transact()?;
+        if !Self::is_out_of_gas(&output.result) {
+            return Ok((output, None));
+        }
+
+        let gas_limit = self.context.evm.env.tx.gas_limit;
+        self.context.evm.env.tx.gas_limit = self
+            .context
+            .evm
+            .env
+            .block
+            .gas_limit()
+            .saturating_to::<u64>();
+        let retry = self.transact();
+        self.context.evm.env.tx.gas_limit = gas_limit;
+
+        let required = match retry {
+            Ok(retry) if !Self::is_out_of_gas(&retry.result) => {
+                Some(retry.gas.intrinsic_gas + retry.gas.execution_gas)
+            }
+            _ => None,
+        };
+        Ok((output, required))
+    }
+
+    fn is_out_of_gas(result: &ExecutionResult<EvmWiringT::HaltReason>) -> bool {
+        let ExecutionResult::Halt { reason, .. } = result else {
+            return false;
+        };
+        [
+            OutOfGasError::Basic,
+            OutOfGasError::MemoryLimit,
+            OutOfGasError::Memory,
+            OutOfGasError::Precompile,
+            OutOfGasError::InvalidOperand,
+            OutOfGasError::ReentrancySentry,
+        ]
+        .into_iter()
+        .any(|error| *reason == HaltReason::OutOfGas(error).into())
+    }
+}
+
 impl<'a, EvmWiringT: EvmWiring> Evm<'a, EvmWiringT>
 where
     EvmWiringT::Transaction: Default,
@@ -425,11 +486,12 @@ mod tests {
     use super::*;
     use crate::{
         db::BenchmarkDB,
-        interpreter::opcode::{PUSH1, SSTORE},
+        interpreter::opcode::*,
         primitives::{
             address, Authorization, Bytecode, EthereumWiring, RecoveredAuthorization, Signature,
             U256,
         },
+        test_utils::evm_with_code,
     };
 
     #[test]
@@ -472,4 +534,32 @@ mod tests {
             U256::from(1)
         );
     }
+
+    #[test]
+    fn dry_run_reports_gas_required_after_out_of_gas() {
+        // writes three fresh storage slots.
+        let code = [
+            PUSH1, 1, PUSH1, 1, SSTORE, PUSH1, 1, PUSH1, 2, SSTORE, PUSH1, 1, PUSH1, 3, SSTORE,
+            STOP,
+        ];
+        let mut evm = evm_with_code(&code, SpecId::LATEST);
+        evm.tx_mut().gas_limit = 30_000;
+
+        let (output, required) = evm.transact_dry_run().unwrap();
+        assert!(matches!(
+            output.result,
+            ExecutionResult::Halt {
+                reason: HaltReason::OutOfGas(_),
+                gas_used: 30_000,
+            }
+        ));
+        assert_eq!(evm.tx().gas_limit, 30_000);
+        let required = required.unwrap();
+        assert!(required > 30_000);
+
+        evm.tx_mut().gas_limit = required;
+        let (output, required) = evm.transact_dry_run().unwrap();
+        assert!(output.result.is_success());
+        assert_eq!(required, None);
+    }
 }
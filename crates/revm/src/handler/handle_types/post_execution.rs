@@ -57,7 +57,7 @@ impl<'a, EvmWiringT: EvmWiring + 'a> PostExecutionHandler<'a, EvmWiringT> {
             refund: Arc::new(mainnet::refund::<EvmWiringT, SPEC>),
             reimburse_caller: Arc::new(mainnet::reimburse_caller::<EvmWiringT>),
             reward_beneficiary: Arc::new(mainnet::reward_beneficiary::<EvmWiringT, SPEC>),
-            output: Arc::new(mainnet::output::<EvmWiringT>),
+            output: Arc::new(mainnet::output::<EvmWiringT, SPEC>),
             end: Arc::new(mainnet::end::<EvmWiringT>),
             clear: Arc::new(mainnet::clear::<EvmWiringT>),
         }
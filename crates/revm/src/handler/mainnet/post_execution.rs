@@ -1,10 +1,11 @@
+use super::initial_tx_gas;
 use crate::{
     interpreter::{Gas, SuccessOrHalt},
     primitives::{
-        Block, EVMError, EVMResult, EVMResultGeneric, ExecutionResult, ResultAndState, Spec,
-        SpecId, SpecId::LONDON, Transaction, U256,
+        Block, EVMError, EVMResult, EVMResultGeneric, EnvWiring, ExecutionResult, ResultAndState,
+        ResultGas, SelfDestructRecord, Spec, SpecId, SpecId::LONDON, Transaction, U256,
     },
-    Context, EvmWiring, FrameResult,
+    Context, EvmWiring, FrameResult, JournalEntry,
 };
 
 /// Mainnet end handle does not change the output.
@@ -24,23 +25,38 @@ pub fn clear<EvmWiringT: EvmWiring>(context: &mut Context<EvmWiringT>) {
     context.evm.inner.journaled_state.clear();
 }
 
+/// Returns the fee paid to the beneficiary and the base fee of the gas used, as settled by
+/// [`reward_beneficiary`].
+#[inline]
+fn fees<EvmWiringT: EvmWiring, SPEC: Spec>(env: &EnvWiring<EvmWiringT>, gas: &Gas) -> (U256, U256) {
+    let effective_gas_price = env.effective_gas_price();
+    let gas_used = U256::from(gas.spent() - gas.refunded() as u64);
+
+    // EIP-1559 discard basefee for coinbase transfer. Basefee amount of gas is discarded.
+    if SPEC::enabled(LONDON) {
+        let base_fee = env.settlement_base_fee();
+        (
+            effective_gas_price.saturating_sub(base_fee) * gas_used,
+            effective_gas_price.min(base_fee) * gas_used,
+        )
+    } else {
+        (effective_gas_price * gas_used, U256::ZERO)
+    }
+}
+
 /// Reward beneficiary with gas fee.
 #[inline]
 pub fn reward_beneficiary<EvmWiringT: EvmWiring, SPEC: Spec>(
     context: &mut Context<EvmWiringT>,
     gas: &Gas,
 ) -> EVMResultGeneric<(), EvmWiringT> {
-    let beneficiary = *context.evm.env.block.coinbase();
-    let effective_gas_price = context.evm.env.effective_gas_price();
+    if context.evm.env.cfg.is_fee_settlement_disabled() {
+        return Ok(());
+    }
+    let beneficiary = context.evm.env.fee_recipient();
+    let (reward, base_fee) = fees::<EvmWiringT, SPEC>(&context.evm.env, gas);
 
     // transfer fee to coinbase/beneficiary.
-    // EIP-1559 discard basefee for coinbase transfer. Basefee amount of gas is discarded.
-    let coinbase_gas_price = if SPEC::enabled(LONDON) {
-        effective_gas_price.saturating_sub(*context.evm.env.block.basefee())
-    } else {
-        effective_gas_price
-    };
-
     let coinbase_account = context
         .evm
         .inner
@@ -49,11 +65,23 @@ pub fn reward_beneficiary<EvmWiringT: EvmWiring, SPEC: Spec>(
         .map_err(EVMError::Database)?;
 
     coinbase_account.data.mark_touch();
-    coinbase_account.data.info.balance = coinbase_account
-        .data
-        .info
-        .balance
-        .saturating_add(coinbase_gas_price * U256::from(gas.spent() - gas.refunded() as u64));
+    coinbase_account.data.info.balance = coinbase_account.data.info.balance.saturating_add(reward);
+
+    // Credit the base fee instead of burning it, if configured.
+    if let Some(recipient) = context.evm.env.cfg.base_fee_recipient {
+        if SPEC::enabled(LONDON) {
+            let recipient_account = context
+                .evm
+                .inner
+                .journaled_state
+                .load_account(recipient, &mut context.evm.inner.db)
+                .map_err(EVMError::Database)?;
+
+            recipient_account.data.mark_touch();
+            recipient_account.data.info.balance =
+                recipient_account.data.info.balance.saturating_add(base_fee);
+        }
+    }
 
     Ok(())
 }
@@ -76,6 +104,9 @@ pub fn reimburse_caller<EvmWiringT: EvmWiring>(
     context: &mut Context<EvmWiringT>,
     gas: &Gas,
 ) -> EVMResultGeneric<(), EvmWiringT> {
+    if context.evm.env.cfg.is_fee_settlement_disabled() {
+        return Ok(());
+    }
     let caller = *context.evm.env.tx.caller();
     let effective_gas_price = context.evm.env.effective_gas_price();
 
@@ -97,12 +128,33 @@ pub fn reimburse_caller<EvmWiringT: EvmWiring>(
 
 /// Main return handle, returns the output of the transaction.
 #[inline]
-pub fn output<EvmWiringT: EvmWiring>(
+pub fn output<EvmWiringT: EvmWiring, SPEC: Spec>(
     context: &mut Context<EvmWiringT>,
     result: FrameResult,
 ) -> EVMResult<EvmWiringT> {
     context.evm.take_error().map_err(EVMError::Database)?;
 
+    let env = &context.evm.inner.env;
+    let is_create = env.tx.kind().is_create();
+    let intrinsic_gas = initial_tx_gas::<EvmWiringT, SPEC>(env);
+    let (reward, base_fee) = if env.cfg.is_fee_settlement_disabled() {
+        (U256::ZERO, U256::ZERO)
+    } else {
+        fees::<EvmWiringT, SPEC>(env, result.gas())
+    };
+    // the base fee is burned unless it is credited to a recipient.
+    let burned = if env.cfg.base_fee_recipient.is_some() {
+        U256::ZERO
+    } else {
+        base_fee
+    };
+    // the coinbase receives the priority fee and the value sent to it during execution.
+    let coinbase = *env.block.coinbase();
+    let mut coinbase_reward = context.evm.journaled_state.value_received(coinbase);
+    if env.fee_recipient() == coinbase {
+        coinbase_reward = coinbase_reward.saturating_add(reward);
+    }
+
     // used gas with refund calculated.
     let gas_refunded = result.gas().refunded() as u64;
     let final_gas_used = result.gas().spent() - gas_refunded;
@@ -110,6 +162,26 @@ pub fn output<EvmWiringT: EvmWiring>(
     let instruction_result = result.into_interpreter_result();
 
     // reset journal and return present state.
+    let selfdestructed = context
+        .evm
+        .journaled_state
+        .journal
+        .iter()
+        .flatten()
+        .filter_map(|entry| match *entry {
+            JournalEntry::AccountDestroyed {
+                address,
+                target,
+                had_balance,
+                ..
+            } => Some(SelfDestructRecord {
+                address,
+                target,
+                balance: had_balance,
+            }),
+            _ => None,
+        })
+        .collect();
     let (state, logs) = context.evm.journaled_state.finalize();
 
     let result = match SuccessOrHalt::<EvmWiringT>::from(instruction_result.result) {
@@ -137,5 +209,395 @@ pub fn output<EvmWiringT: EvmWiring>(
         }
     };
 
-    Ok(ResultAndState { result, state })
+    Ok(ResultAndState {
+        result,
+        state,
+        gas: ResultGas::new(is_create, intrinsic_gas, final_gas_used, gas_refunded),
+        coinbase_reward,
+        minted: U256::ZERO,
+        burned,
+        selfdestructed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::{CacheDB, EmptyDB},
+        interpreter::opcode::*,
+        primitives::{
+            address, AccountInfo, Address, Bytecode, EVMError, EthereumWiring, ExecutionResult,
+            InvalidTransaction, ResultAndState, SpecId, TxKind, U256,
+        },
+        test_utils::{deploy_and_call, evm_with_code, CALLER, CONTRACT},
+        Evm,
+    };
+
+    fn coinbase_balance_after_transfer(base_fee_to_coinbase: bool) -> U256 {
+        let caller = address!("1000000000000000000000000000000000000000");
+        let coinbase = address!("c000000000000000000000000000000000000000");
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(10u64.pow(18))));
+
+        let mut evm = Evm::<EthereumWiring<CacheDB<EmptyDB>, ()>>::builder()
+            .with_db(db)
+            .with_default_ext_ctx()
+            .with_spec_id(SpecId::CANCUN)
+            .modify_cfg_env(|cfg| {
+                cfg.base_fee_recipient = base_fee_to_coinbase.then_some(coinbase);
+            })
+            .modify_block_env(|block| {
+                block.coinbase = coinbase;
+                block.basefee = U256::from(10);
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(address!("2000000000000000000000000000000000000000"));
+                tx.gas_limit = 21_000;
+                tx.gas_price = U256::from(15);
+            })
+            .build();
+
+        let result = evm.transact().unwrap();
+        assert_eq!(result.result.gas_used(), 21_000);
+        result.state[&coinbase].info.balance
+    }
+
+    #[test]
+    fn base_fee_is_burned_by_default() {
+        assert_eq!(
+            coinbase_balance_after_transfer(false),
+            U256::from(5 * 21_000)
+        );
+    }
+
+    #[test]
+    fn base_fee_is_credited_to_recipient() {
+        assert_eq!(
+            coinbase_balance_after_transfer(true),
+            U256::from(15 * 21_000)
+        );
+    }
+
+    #[test]
+    fn fee_settlement_can_be_disabled() {
+        let caller = address!("1000000000000000000000000000000000000000");
+        let coinbase = address!("c000000000000000000000000000000000000000");
+        let balance = U256::from(1_000_000);
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(caller, AccountInfo::from_balance(balance));
+
+        let mut evm = Evm::<EthereumWiring<CacheDB<EmptyDB>, ()>>::builder()
+            .with_db(db)
+            .with_default_ext_ctx()
+            .with_spec_id(SpecId::CANCUN)
+            .modify_cfg_env(|cfg| cfg.disable_fee_settlement = true)
+            .modify_block_env(|block| {
+                block.coinbase = coinbase;
+                block.basefee = U256::from(10);
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(address!("2000000000000000000000000000000000000000"));
+                tx.gas_limit = 30_000;
+                // the caller could not pay for the gas limit.
+                tx.gas_price = U256::from(100);
+                tx.value = U256::from(1);
+            })
+            .build();
+
+        let result = evm.transact().unwrap();
+        assert_eq!(result.result.gas_used(), 21_000);
+        assert_eq!(result.state[&caller].info.balance, balance - U256::from(1));
+        assert_eq!(result.state[&caller].info.nonce, 1);
+        assert!(!result.state.contains_key(&coinbase));
+        assert_eq!(result.coinbase_reward, U256::ZERO);
+    }
+
+    #[test]
+    fn fees_are_paid_to_configured_fee_recipient() {
+        let caller = address!("1000000000000000000000000000000000000000");
+        let contract = address!("2000000000000000000000000000000000000000");
+        let coinbase = address!("c000000000000000000000000000000000000000");
+        let fee_recipient = address!("fee0000000000000000000000000000000000000");
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(10u64.pow(18))));
+        // SSTORE(0, COINBASE)
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(
+                [COINBASE, PUSH1, 0, SSTORE, STOP].into(),
+            )),
+        );
+
+        let mut evm = Evm::<EthereumWiring<CacheDB<EmptyDB>, ()>>::builder()
+            .with_db(db)
+            .with_default_ext_ctx()
+            .with_spec_id(SpecId::CANCUN)
+            .modify_cfg_env(|cfg| cfg.fee_recipient = Some(fee_recipient))
+            .modify_block_env(|block| {
+                block.coinbase = coinbase;
+                block.basefee = U256::from(10);
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(contract);
+                tx.gas_limit = 100_000;
+                tx.gas_price = U256::from(15);
+            })
+            .build();
+
+        let result = evm.transact().unwrap();
+        assert!(result.result.is_success());
+        let priority_fee = U256::from(5 * result.result.gas_used());
+        assert_eq!(result.state[&fee_recipient].info.balance, priority_fee);
+        assert!(!result.state.contains_key(&coinbase));
+        assert_eq!(result.coinbase_reward, U256::ZERO);
+        assert_eq!(
+            result.state[&contract].storage[&U256::ZERO].present_value,
+            coinbase.into_word().into()
+        );
+    }
+
+    #[test]
+    fn gas_price_below_base_fee_settles_with_clamped_price() {
+        let coinbase = address!("0000000000000000000000000000000000003000");
+        let balance = U256::from(10u64.pow(18));
+
+        let mut evm = evm_with_code(&[STOP], SpecId::LATEST);
+        evm.db_mut()
+            .insert_account_info(CALLER, AccountInfo::from_balance(balance));
+        evm.block_mut().basefee = U256::from(100);
+        evm.block_mut().coinbase = coinbase;
+        let tx = evm.tx_mut();
+        tx.gas_limit = 30_000;
+        tx.gas_price = U256::from(50);
+        tx.gas_priority_fee = Some(U256::from(10));
+
+        assert!(matches!(
+            evm.transact(),
+            Err(EVMError::Transaction(
+                InvalidTransaction::GasPriceLessThanBasefee
+            ))
+        ));
+
+        // min(max fee, priority fee) is paid for the used gas, all of it to the coinbase.
+        evm.cfg_mut().allow_gas_price_below_base_fee = true;
+        let out = evm.transact().unwrap();
+        assert_eq!(out.result.gas_used(), 21_000);
+        let fee = U256::from(10 * 21_000);
+        assert_eq!(out.state[&CALLER].info.balance, balance - fee);
+        assert_eq!(out.state[&coinbase].info.balance, fee);
+        assert_eq!(out.coinbase_reward, fee);
+    }
+
+    #[test]
+    fn result_gas_adds_up_to_gas_used() {
+        // clears a storage slot, which is refunded.
+        let mut evm = evm_with_code(&[PUSH1, 0, PUSH1, 1, SSTORE, STOP], SpecId::LATEST);
+        evm.db_mut()
+            .insert_account_storage(CONTRACT, U256::from(1), U256::from(5))
+            .unwrap();
+
+        let ResultAndState { result, gas, .. } = evm.transact().unwrap();
+        assert!(!gas.is_create);
+        assert_eq!(gas.intrinsic_gas, 21_000);
+        assert!(gas.refund > 0);
+        assert_eq!(
+            gas.intrinsic_gas + gas.execution_gas - gas.refund,
+            result.gas_used()
+        );
+        assert_eq!(gas.gas_used(), result.gas_used());
+
+        evm.tx_mut().transact_to = TxKind::Create;
+        let ResultAndState { result, gas, .. } = evm.transact().unwrap();
+        assert!(gas.is_create);
+        assert_eq!(gas.refund, 0);
+        assert_eq!(gas.gas_used(), result.gas_used());
+    }
+
+    /// Returns an EVM calling `code` held by a contract with 100 wei, paying a priority fee of
+    /// 2 over a base fee of 5 to `coinbase`.
+    fn evm_paying_coinbase(
+        code: &[u8],
+        coinbase: Address,
+    ) -> Evm<'static, EthereumWiring<CacheDB<EmptyDB>, ()>> {
+        let mut evm = evm_with_code(code, SpecId::LATEST);
+        let db = evm.db_mut();
+        db.accounts.get_mut(&CONTRACT).unwrap().info.balance = U256::from(100);
+        db.insert_account_info(CALLER, AccountInfo::from_balance(U256::from(10_000_000)));
+        evm.block_mut().coinbase = coinbase;
+        evm.block_mut().basefee = U256::from(5);
+        let tx = evm.tx_mut();
+        tx.gas_limit = 100_000;
+        tx.gas_price = U256::from(10);
+        tx.gas_priority_fee = Some(U256::from(2));
+        evm
+    }
+
+    #[test]
+    fn coinbase_reward_includes_priority_fee_and_transfers() {
+        let coinbase = address!("0000000000000000000000000000000000c0ffee");
+        // CALL(gas, coinbase, 7, 0, 0, 0, 0)
+        let mut code = vec![PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 7, PUSH20];
+        code.extend_from_slice(coinbase.as_slice());
+        code.extend_from_slice(&[GAS, CALL, STOP]);
+
+        let ResultAndState {
+            result,
+            state,
+            coinbase_reward,
+            minted,
+            burned,
+            ..
+        } = evm_paying_coinbase(&code, coinbase).transact().unwrap();
+        assert!(result.is_success());
+        let expected = U256::from(2 * result.gas_used() + 7);
+        assert_eq!(coinbase_reward, expected);
+        assert_eq!(state[&coinbase].info.balance, expected);
+        // the base fee is burned.
+        assert_eq!(minted, U256::ZERO);
+        assert_eq!(burned, U256::from(5 * result.gas_used()));
+    }
+
+    #[test]
+    fn coinbase_reward_skips_reverted_transfers_and_counts_selfdestructs() {
+        let reverting = address!("0000000000000000000000000000000000001001");
+        let coinbase = address!("0000000000000000000000000000000000c0ffee");
+
+        // CALL(gas, reverting, 0, 0, 0, 0, 0); SELFDESTRUCT(coinbase)
+        let mut code = vec![PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH20];
+        code.extend_from_slice(reverting.as_slice());
+        code.extend_from_slice(&[GAS, CALL, POP, PUSH20]);
+        code.extend_from_slice(coinbase.as_slice());
+        code.push(SELFDESTRUCT);
+        // CALL(gas, coinbase, 7, 0, 0, 0, 0); REVERT(0, 0)
+        let mut reverting_code = vec![PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 7, PUSH20];
+        reverting_code.extend_from_slice(coinbase.as_slice());
+        reverting_code.extend_from_slice(&[GAS, CALL, PUSH1, 0, PUSH1, 0, REVERT]);
+
+        let mut evm = evm_paying_coinbase(&code, coinbase);
+        evm.db_mut().insert_account_info(
+            reverting,
+            AccountInfo {
+                balance: U256::from(100),
+                ..AccountInfo::from_bytecode(Bytecode::new_legacy(reverting_code.into()))
+            },
+        );
+
+        let ResultAndState {
+            result,
+            state,
+            coinbase_reward,
+            ..
+        } = evm.transact().unwrap();
+        assert!(result.is_success());
+        let expected = U256::from(2 * result.gas_used() + 100);
+        assert_eq!(coinbase_reward, expected);
+        assert_eq!(state[&coinbase].info.balance, expected);
+    }
+
+    #[test]
+    fn selfdestructed_records_target_and_balance() {
+        use crate::primitives::SelfDestructRecord;
+
+        let beneficiary = address!("0000000000000000000000000000000000002000");
+        let mut code = vec![PUSH20];
+        code.extend_from_slice(beneficiary.as_slice());
+        code.push(SELFDESTRUCT);
+
+        let transact = |spec_id| {
+            let mut evm = evm_with_code(&code, spec_id);
+            evm.db_mut()
+                .accounts
+                .get_mut(&CONTRACT)
+                .unwrap()
+                .info
+                .balance = U256::from(1_000);
+            evm.transact().unwrap()
+        };
+
+        let out = transact(SpecId::SHANGHAI);
+        assert!(out.result.is_success());
+        assert_eq!(
+            out.selfdestructed,
+            vec![SelfDestructRecord {
+                address: CONTRACT,
+                target: beneficiary,
+                balance: U256::from(1_000),
+            }]
+        );
+        assert_eq!(out.state[&beneficiary].info.balance, U256::from(1_000));
+        assert!(out.state[&CONTRACT].is_selfdestructed());
+
+        // EIP-6780: the account was not created in the transaction and is not deleted.
+        let out = transact(SpecId::CANCUN);
+        assert!(out.result.is_success());
+        assert!(out.selfdestructed.is_empty());
+        assert_eq!(out.state[&beneficiary].info.balance, U256::from(1_000));
+        assert!(!out.state[&CONTRACT].is_selfdestructed());
+    }
+
+    #[test]
+    fn refund_does_not_carry_over_between_transactions() {
+        let other = address!("0000000000000000000000000000000000002000");
+        // SSTORE(0, 0) clearing the non-zero slot
+        let code = [PUSH1, 0, PUSH1, 0, SSTORE, STOP];
+
+        let mut evm = evm_with_code(&code, SpecId::LATEST);
+        let db = evm.db_mut();
+        db.insert_account_info(
+            other,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+        );
+        for address in [CONTRACT, other] {
+            db.insert_account_storage(address, U256::ZERO, U256::from(1))
+                .unwrap();
+        }
+
+        let ExecutionResult::Success { gas_refunded, .. } = evm.transact_commit().unwrap() else {
+            panic!("first transaction failed");
+        };
+        assert_eq!(gas_refunded, 4800);
+
+        evm.tx_mut().transact_to = TxKind::Call(other);
+        evm.tx_mut().nonce = 1;
+        let ExecutionResult::Success {
+            gas_refunded: second_refund,
+            ..
+        } = evm.transact_commit().unwrap()
+        else {
+            panic!("second transaction failed");
+        };
+        assert_eq!(second_refund, gas_refunded);
+    }
+
+    #[test]
+    fn top_level_revert_exposes_data_and_reason() {
+        use crate::primitives::Bytes;
+
+        // abi encoded Error("not allowed")
+        let message = b"not allowed";
+        let mut revert_data = vec![0x08, 0xc3, 0x79, 0xa0];
+        revert_data.extend_from_slice(&U256::from(32).to_be_bytes::<32>());
+        revert_data.extend_from_slice(&U256::from(message.len()).to_be_bytes::<32>());
+        let mut padded = [0u8; 32];
+        padded[..message.len()].copy_from_slice(message);
+        revert_data.extend_from_slice(&padded);
+
+        // copies the revert data appended to the code into memory and reverts with it.
+        let len = revert_data.len() as u8;
+        let mut code = vec![
+            PUSH1, len, PUSH1, 12, PUSH1, 0, CODECOPY, PUSH1, len, PUSH1, 0, REVERT,
+        ];
+        code.extend_from_slice(&revert_data);
+
+        let result = deploy_and_call(&code, SpecId::LATEST).result;
+        assert!(matches!(result, ExecutionResult::Revert { .. }));
+        assert_eq!(result.output(), Some(&Bytes::from(revert_data)));
+        assert_eq!(result.revert_reason().as_deref(), Some("not allowed"));
+    }
 }
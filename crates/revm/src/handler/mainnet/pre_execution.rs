@@ -5,11 +5,12 @@
 use crate::{
     precompile::PrecompileSpecId,
     primitives::{
-        eip7702, Account, Block, Bytecode, EVMError, EVMResultGeneric, EnvWiring, Spec, SpecId,
-        Transaction, BLOCKHASH_STORAGE_ADDRESS, PRAGUE, U256,
+        eip7702, Account, Block, EVMError, EVMResultGeneric, EnvWiring, Spec, SpecId, Transaction,
+        BLOCKHASH_STORAGE_ADDRESS, PRAGUE, U256,
     },
     Context, ContextPrecompiles, EvmWiring,
 };
+use std::vec::Vec;
 
 /// Main precompile load
 #[inline]
@@ -68,7 +69,9 @@ pub fn deduct_caller_inner<EvmWiringT: EvmWiring, SPEC: Spec>(
     }
 
     // set new caller account balance.
-    caller_account.info.balance = caller_account.info.balance.saturating_sub(gas_cost);
+    if !env.cfg.is_fee_settlement_disabled() {
+        caller_account.info.balance = caller_account.info.balance.saturating_sub(gas_cost);
+    }
 
     // bump the nonce for calls. Nonce for CREATE will be bumped in `handle_create`.
     if env.tx.kind().is_call() {
@@ -117,60 +120,15 @@ pub fn apply_eip7702_auth_list<EvmWiringT: EvmWiring, SPEC: Spec>(
         return Ok(0);
     };
 
-    let mut refunded_accounts = 0;
-    for authorization in authorization_list.recovered_iter() {
-        // 1. recover authority and authorized addresses.
-        // authority = ecrecover(keccak(MAGIC || rlp([chain_id, address, nonce])), y_parity, r, s]
-        let Some(authority) = authorization.authority() else {
-            continue;
-        };
-
-        // 2. Verify the chain id is either 0 or the chain's current ID.
-        if !authorization.chain_id().is_zero()
-            && authorization.chain_id() != U256::from(context.evm.inner.env.cfg.chain_id)
-        {
-            continue;
-        }
-
-        // warm authority account and check nonce.
-        // 3. Add authority to accessed_addresses (as defined in EIP-2929.)
-        let mut authority_acc = context
-            .evm
-            .inner
-            .journaled_state
-            .load_code(authority, &mut context.evm.inner.db)
-            .map_err(EVMError::Database)?;
-
-        // 4. Verify the code of authority is either empty or already delegated.
-        if let Some(bytecode) = &authority_acc.info.code {
-            // if it is not empty and it is not eip7702
-            if !bytecode.is_empty() && !bytecode.is_eip7702() {
-                continue;
-            }
-        }
-
-        // 5. Verify the nonce of authority is equal to nonce.
-        if authorization.nonce() != authority_acc.info.nonce {
-            continue;
-        }
-
-        // 6. Refund the sender PER_EMPTY_ACCOUNT_COST - PER_AUTH_BASE_COST gas if authority exists in the trie.
-        if !authority_acc.is_empty() {
-            refunded_accounts += 1;
-        }
-
-        // 7. Set the code of authority to be 0xef0100 || address. This is a delegation designation.
-        let bytecode = Bytecode::new_eip7702(authorization.address);
-        authority_acc.info.code_hash = bytecode.hash_slow();
-        authority_acc.info.code = Some(bytecode);
-
-        // 8. Increase the nonce of authority by one.
-        authority_acc.info.nonce = authority_acc.info.nonce.saturating_add(1);
-        authority_acc.mark_touch();
-    }
+    let authorization_list: Vec<_> = authorization_list.recovered_iter().collect();
+    let gas = context
+        .evm
+        .inner
+        .apply_authorization_list(&authorization_list)
+        .map_err(EVMError::Database)?;
 
-    let refunded_gas =
-        refunded_accounts * (eip7702::PER_EMPTY_ACCOUNT_COST - eip7702::PER_AUTH_BASE_COST);
+    // the intrinsic gas charged PER_EMPTY_ACCOUNT_COST for each authorization.
+    let refunded_gas = authorization_list.len() as u64 * eip7702::PER_EMPTY_ACCOUNT_COST - gas;
 
     Ok(refunded_gas)
 }
@@ -48,13 +48,8 @@ where
     Ok(())
 }
 
-/// Validate initial transaction gas.
-pub fn validate_initial_tx_gas<EvmWiringT: EvmWiring, SPEC: Spec>(
-    env: &EnvWiring<EvmWiringT>,
-) -> EVMResultGeneric<u64, EvmWiringT>
-where
-    <EvmWiringT::Transaction as TransactionValidation>::ValidationError: From<InvalidTransaction>,
-{
+/// Returns the intrinsic gas of the transaction.
+pub fn initial_tx_gas<EvmWiringT: EvmWiring, SPEC: Spec>(env: &EnvWiring<EvmWiringT>) -> u64 {
     let input = &env.tx.data();
     let is_create = env.tx.kind().is_create();
     let access_list = env.tx.access_list();
@@ -65,13 +60,23 @@ where
         .map(|l| l.len() as u64)
         .unwrap_or_default();
 
-    let initial_gas_spend = gas::validate_initial_tx_gas(
+    gas::validate_initial_tx_gas(
         SPEC::SPEC_ID,
         input,
         is_create,
         access_list,
         authorization_list_num,
-    );
+    )
+}
+
+/// Validate initial transaction gas.
+pub fn validate_initial_tx_gas<EvmWiringT: EvmWiring, SPEC: Spec>(
+    env: &EnvWiring<EvmWiringT>,
+) -> EVMResultGeneric<u64, EvmWiringT>
+where
+    <EvmWiringT::Transaction as TransactionValidation>::ValidationError: From<InvalidTransaction>,
+{
+    let initial_gas_spend = initial_tx_gas::<EvmWiringT, SPEC>(env);
 
     // Additional check to see if limit is big enough to cover initial gas.
     if initial_gas_spend > env.tx.gas_limit() {
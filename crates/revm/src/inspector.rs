@@ -1,3 +1,5 @@
+mod call_tree;
+mod console_log;
 #[cfg(feature = "std")]
 mod customprinter;
 #[cfg(all(feature = "std", feature = "serde-json"))]
@@ -5,6 +7,9 @@ mod eip3155;
 mod gas;
 mod handler_register;
 mod noop;
+mod opcode_budget;
+mod opcode_coverage;
+mod out_of_gas;
 
 pub use handler_register::{inspector_handle_register, GetInspector};
 
@@ -19,12 +24,17 @@ use auto_impl::auto_impl;
 
 /// [Inspector] implementations.
 pub mod inspectors {
+    pub use super::call_tree::{CallTreeFrame, CallTreeInspector, CallTreeKind};
+    pub use super::console_log::{ConsoleLogInspector, CONSOLE_ADDRESS};
     #[cfg(feature = "std")]
     pub use super::customprinter::CustomPrintTracer;
     #[cfg(all(feature = "std", feature = "serde-json"))]
     pub use super::eip3155::TracerEip3155;
     pub use super::gas::GasInspector;
     pub use super::noop::NoOpInspector;
+    pub use super::opcode_budget::OpcodeBudgetInspector;
+    pub use super::opcode_coverage::OpcodeCoverageInspector;
+    pub use super::out_of_gas::OutOfGasInspector;
 }
 
 /// EVM [Interpreter] callbacks.
@@ -52,6 +62,12 @@ pub trait Inspector<EvmWiringT: EvmWiring> {
     /// # Example
     ///
     /// To get the current opcode, use `interp.current_opcode()`.
+    ///
+    /// Setting `interp.instruction_result` to anything other than
+    /// [`InstructionResult::Continue`] skips the instruction and ends the frame with that
+    /// result, which can be used to inject faults, see [`inspectors::OutOfGasInspector`].
+    ///
+    /// [`InstructionResult::Continue`]: crate::interpreter::InstructionResult::Continue
     #[inline]
     fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<EvmWiringT>) {
         let _ = interp;
@@ -164,6 +180,27 @@ pub trait Inspector<EvmWiringT: EvmWiring> {
         outcome
     }
 
+    /// Called after a successful `SLOAD` with the loaded `value`.
+    #[inline]
+    fn sload(&mut self, address: Address, key: U256, value: U256) {
+        let _ = address;
+        let _ = key;
+        let _ = value;
+    }
+
+    /// Called after a successful `SSTORE`.
+    ///
+    /// `original` is the value at the start of the transaction, `current` the value before
+    /// this store and `new` the stored value.
+    #[inline]
+    fn sstore(&mut self, address: Address, key: U256, original: U256, current: U256, new: U256) {
+        let _ = address;
+        let _ = key;
+        let _ = original;
+        let _ = current;
+        let _ = new;
+    }
+
     /// Called when a contract has been self-destructed with funds transferred to target.
     #[inline]
     fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
@@ -171,4 +208,15 @@ pub trait Inspector<EvmWiringT: EvmWiring> {
         let _ = target;
         let _ = value;
     }
+
+    /// Called when a frame is entered at the call depth set in
+    /// [`CfgEnv::call_depth_warning`](crate::primitives::CfgEnv::call_depth_warning), before
+    /// [`Self::initialize_interp`]. The top level frame is at depth one.
+    ///
+    /// Execution continues normally, this only flags deep recursion.
+    #[inline]
+    fn call_depth_reached(&mut self, context: &mut EvmContext<EvmWiringT>, depth: u64) {
+        let _ = context;
+        let _ = depth;
+    }
 }
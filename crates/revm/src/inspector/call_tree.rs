@@ -0,0 +1,341 @@
+//! CallTreeInspector. Records the tree of call frames of an execution.
+
+use crate::{
+    interpreter::{
+        CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome, InstructionResult,
+    },
+    primitives::{Address, Bytes, CreateScheme, U256},
+    EvmContext, EvmWiring, Inspector,
+};
+use std::vec::Vec;
+
+/// Kind of a [`CallTreeFrame`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallTreeKind {
+    /// Message call.
+    Call(CallScheme),
+    /// Contract creation.
+    Create(CreateScheme),
+}
+
+/// Call frame recorded by [`CallTreeInspector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallTreeFrame {
+    /// Kind of the frame.
+    pub kind: CallTreeKind,
+    /// Caller of the frame.
+    pub from: Address,
+    /// Address whose code is executed. For creates, the created address if successful.
+    pub to: Option<Address>,
+    /// Call value. For `DELEGATECALL` this is the apparent value.
+    pub value: U256,
+    /// Call input or init code.
+    pub input: Bytes,
+    /// Returned data.
+    pub output: Bytes,
+    /// Gas limit of the frame.
+    pub gas_limit: u64,
+    /// Gas spent by the frame, including the gas spent by the frames it called.
+    pub gas_used: u64,
+    /// Result of the frame.
+    pub result: InstructionResult,
+    /// Frames called from this frame, in execution order.
+    pub calls: Vec<CallTreeFrame>,
+    /// Number of frames below this frame that are not recorded because they are deeper than
+    /// the maximum depth, see [`CallTreeInspector::with_max_depth`].
+    pub elided_calls: usize,
+}
+
+impl CallTreeFrame {
+    /// Returns the result of the frame if it reverted or halted.
+    pub fn error(&self) -> Option<InstructionResult> {
+        (!self.result.is_ok()).then_some(self.result)
+    }
+
+    /// Returns the gas spent by the frame itself, excluding the gas spent by recorded calls.
+    ///
+    /// Gas of [elided](Self::elided_calls) calls is attributed to the frame.
+    pub fn self_gas_used(&self) -> u64 {
+        let calls: u64 = self.calls.iter().map(|call| call.gas_used).sum();
+        self.gas_used.saturating_sub(calls)
+    }
+}
+
+/// [Inspector] that builds the tree of call and create frames of an execution.
+///
+/// Calls to precompiles and frames that revert or halt are recorded like any other frame.
+#[derive(Clone, Debug, Default)]
+pub struct CallTreeInspector {
+    /// Frames that have started and not yet ended.
+    stack: Vec<CallTreeFrame>,
+    /// Finished top level frames.
+    roots: Vec<CallTreeFrame>,
+    /// Depth of the active frame, including frames that are not recorded.
+    depth: usize,
+    /// Maximum depth of recorded frames.
+    max_depth: Option<usize>,
+}
+
+impl CallTreeInspector {
+    /// Creates an inspector that records frames up to `max_depth` levels deep, the top level
+    /// frame being at level one.
+    ///
+    /// Deeper frames are only counted, in [`CallTreeFrame::elided_calls`] of their closest
+    /// recorded ancestor. This bounds the memory used for deeply nested executions.
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        Self {
+            max_depth: Some(max_depth),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the recorded top level frames. A single transaction produces one root.
+    pub fn roots(&self) -> &[CallTreeFrame] {
+        &self.roots
+    }
+
+    /// Consumes the inspector and returns the recorded top level frames.
+    pub fn into_roots(self) -> Vec<CallTreeFrame> {
+        self.roots
+    }
+
+    /// Records `frame`, or counts it if it is too deep.
+    fn start_frame(&mut self, frame: CallTreeFrame) {
+        self.depth += 1;
+        if self
+            .max_depth
+            .is_some_and(|max_depth| self.depth > max_depth)
+        {
+            if let Some(parent) = self.stack.last_mut() {
+                parent.elided_calls += 1;
+            }
+            return;
+        }
+        self.stack.push(frame);
+    }
+
+    /// Returns `true` if the active frame is recorded.
+    fn is_recorded(&self) -> bool {
+        self.depth == self.stack.len()
+    }
+
+    fn end_frame(&mut self, output: &Bytes, gas_used: u64, result: InstructionResult) {
+        let is_recorded = self.is_recorded();
+        self.depth = self.depth.saturating_sub(1);
+        if !is_recorded {
+            return;
+        }
+        let Some(mut frame) = self.stack.pop() else {
+            return;
+        };
+        frame.output = output.clone();
+        frame.gas_used = gas_used;
+        frame.result = result;
+        match self.stack.last_mut() {
+            Some(parent) => parent.calls.push(frame),
+            None => self.roots.push(frame),
+        }
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for CallTreeInspector {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.start_frame(CallTreeFrame {
+            kind: CallTreeKind::Call(inputs.scheme),
+            from: inputs.caller,
+            to: Some(inputs.bytecode_address),
+            value: inputs.value.get(),
+            input: inputs.input.clone(),
+            output: Bytes::new(),
+            gas_limit: inputs.gas_limit,
+            gas_used: 0,
+            result: InstructionResult::Continue,
+            calls: Vec::new(),
+            elided_calls: 0,
+        });
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.end_frame(
+            outcome.output(),
+            outcome.gas().spent(),
+            *outcome.instruction_result(),
+        );
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.start_frame(CallTreeFrame {
+            kind: CallTreeKind::Create(inputs.scheme),
+            from: inputs.caller,
+            to: None,
+            value: inputs.value,
+            input: inputs.init_code.clone(),
+            output: Bytes::new(),
+            gas_limit: inputs.gas_limit,
+            gas_used: 0,
+            result: InstructionResult::Continue,
+            calls: Vec::new(),
+            elided_calls: 0,
+        });
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        if self.is_recorded() {
+            if let Some(frame) = self.stack.last_mut() {
+                frame.to = outcome.address;
+            }
+        }
+        self.end_frame(
+            outcome.output(),
+            outcome.gas().spent(),
+            *outcome.instruction_result(),
+        );
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{CacheDB, EmptyDB},
+        inspector::inspector_handle_register,
+        interpreter::opcode::*,
+        primitives::{address, AccountInfo, Bytecode, EthereumWiring, TxKind},
+        test_utils::call_chain,
+        Evm,
+    };
+
+    #[test]
+    fn records_reverted_subcall_as_child() {
+        let a = address!("0000000000000000000000000000000000001000");
+        let b = address!("0000000000000000000000000000000000002000");
+        let caller = address!("1000000000000000000000000000000000000000");
+
+        // CALL(gas, b, 0, 0, 0, 0, 0)
+        let mut code_a = vec![PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH20];
+        code_a.extend_from_slice(b.as_slice());
+        code_a.extend_from_slice(&[GAS, CALL, STOP]);
+        let code_b = vec![PUSH1, 0, PUSH1, 0, REVERT];
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (address, code) in [(a, code_a), (b, code_b)] {
+            db.insert_account_info(
+                address,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+            );
+        }
+
+        let mut evm = Evm::<EthereumWiring<CacheDB<EmptyDB>, CallTreeInspector>>::builder()
+            .with_db(db)
+            .with_external_context(CallTreeInspector::default())
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(a);
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let roots = evm.into_context().external.into_roots();
+        assert_eq!(roots.len(), 1);
+        let root = &roots[0];
+        assert_eq!(root.kind, CallTreeKind::Call(CallScheme::Call));
+        assert_eq!((root.from, root.to), (caller, Some(a)));
+        assert_eq!(root.error(), None);
+
+        assert_eq!(root.calls.len(), 1);
+        let child = &root.calls[0];
+        assert_eq!((child.from, child.to), (a, Some(b)));
+        assert_eq!(child.error(), Some(InstructionResult::Revert));
+        assert!(child.calls.is_empty());
+    }
+
+    #[test]
+    fn attributes_subcall_gas_to_parent() {
+        let a = address!("0000000000000000000000000000000000001000");
+        let b = address!("0000000000000000000000000000000000002000");
+
+        // CALL(gas, b, 0, 0, 0, 0, 0)
+        let mut code_a = vec![PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH20];
+        code_a.extend_from_slice(b.as_slice());
+        code_a.extend_from_slice(&[GAS, CALL, STOP]);
+        // PUSH1 (3) + PUSH1 (3) + ADD (3) + POP (2)
+        let code_b = vec![PUSH1, 1, PUSH1, 2, ADD, POP, STOP];
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (address, code) in [(a, code_a), (b, code_b)] {
+            db.insert_account_info(
+                address,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+            );
+        }
+
+        let mut evm = Evm::<EthereumWiring<CacheDB<EmptyDB>, CallTreeInspector>>::builder()
+            .with_db(db)
+            .with_external_context(CallTreeInspector::default())
+            .modify_tx_env(|tx| tx.transact_to = TxKind::Call(a))
+            .append_handler_register(inspector_handle_register)
+            .build();
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let roots = evm.into_context().external.into_roots();
+        let root = &roots[0];
+        let child = &root.calls[0];
+        assert_eq!(child.gas_used, 11);
+        assert_eq!(child.self_gas_used(), 11);
+        assert_eq!(root.gas_used, root.self_gas_used() + child.gas_used);
+        assert!(root.self_gas_used() > 0);
+    }
+
+    #[test]
+    fn elides_frames_below_max_depth() {
+        let caller = address!("1000000000000000000000000000000000000000");
+        let (contracts, db) = call_chain(5);
+
+        let mut evm = Evm::<EthereumWiring<CacheDB<EmptyDB>, CallTreeInspector>>::builder()
+            .with_db(db)
+            .with_external_context(CallTreeInspector::with_max_depth(2))
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(contracts[0]);
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let roots = evm.into_context().external.into_roots();
+        assert_eq!(roots.len(), 1);
+        let root = &roots[0];
+        assert_eq!(root.to, Some(contracts[0]));
+        assert_eq!(root.elided_calls, 0);
+        assert_eq!(root.calls.len(), 1);
+
+        let child = &root.calls[0];
+        assert_eq!(child.to, Some(contracts[1]));
+        assert_eq!(child.result, InstructionResult::Stop);
+        assert!(child.calls.is_empty());
+        assert_eq!(child.elided_calls, 3);
+    }
+}
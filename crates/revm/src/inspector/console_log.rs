@@ -0,0 +1,238 @@
+//! ConsoleLogInspector. Captures Hardhat/Foundry `console.log` calls.
+
+use crate::{
+    interpreter::{CallInputs, CallOutcome},
+    primitives::{address, hex, keccak256, Address, HashMap, B256, I256, U256},
+    EvmContext, EvmWiring, Inspector,
+};
+use std::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Address of the `console.sol` library of Hardhat and Foundry.
+pub const CONSOLE_ADDRESS: Address = address!("000000000000000000636F6e736F6c652e6c6f67");
+
+/// ABI types of `console.log` arguments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ParamType {
+    Uint,
+    Int,
+    String,
+    Bool,
+    Address,
+    Bytes,
+    FixedBytes(usize),
+}
+
+impl ParamType {
+    fn name(self) -> String {
+        match self {
+            Self::Uint => "uint256".into(),
+            Self::Int => "int256".into(),
+            Self::String => "string".into(),
+            Self::Bool => "bool".into(),
+            Self::Address => "address".into(),
+            Self::Bytes => "bytes".into(),
+            Self::FixedBytes(size) => format!("bytes{size}"),
+        }
+    }
+}
+
+/// [Inspector] that decodes and records calls to [`CONSOLE_ADDRESS`].
+///
+/// The `log` overloads of `console.sol` with up to four `uint256`, `string`, `bool` and
+/// `address` arguments are supported, as well as `log(int256)` and the single argument
+/// `logUint`, `logInt`, `logString`, `logBool`, `logAddress`, `logBytes` and `logBytes1` to
+/// `logBytes32` functions. Arguments are formatted and joined with spaces, format specifiers
+/// are not substituted. Calls that do not match a supported signature are ignored.
+///
+/// The calls are still executed as calls to an empty account.
+#[derive(Clone, Debug)]
+pub struct ConsoleLogInspector {
+    /// Argument types by function selector.
+    signatures: HashMap<[u8; 4], Vec<ParamType>>,
+    /// Captured messages.
+    logs: Vec<String>,
+}
+
+impl Default for ConsoleLogInspector {
+    fn default() -> Self {
+        const LOG_TYPES: [ParamType; 4] = [
+            ParamType::Uint,
+            ParamType::String,
+            ParamType::Bool,
+            ParamType::Address,
+        ];
+
+        let mut signatures = HashMap::new();
+        let mut insert = |name: &str, params: Vec<ParamType>| {
+            let names: Vec<_> = params.iter().map(|param| param.name()).collect();
+            let signature = format!("{name}({})", names.join(","));
+            let selector = keccak256(signature.as_bytes())[..4].try_into().unwrap();
+            signatures.insert(selector, params);
+        };
+
+        let mut combinations = vec![Vec::new()];
+        for _ in 0..4 {
+            combinations = combinations
+                .iter()
+                .flat_map(|params: &Vec<ParamType>| {
+                    LOG_TYPES.iter().map(move |param| {
+                        let mut params = params.clone();
+                        params.push(*param);
+                        params
+                    })
+                })
+                .collect();
+            for params in &combinations {
+                insert("log", params.clone());
+            }
+        }
+        insert("log", vec![ParamType::Int]);
+        for (name, param) in [
+            ("logUint", ParamType::Uint),
+            ("logInt", ParamType::Int),
+            ("logString", ParamType::String),
+            ("logBool", ParamType::Bool),
+            ("logAddress", ParamType::Address),
+            ("logBytes", ParamType::Bytes),
+        ] {
+            insert(name, vec![param]);
+        }
+        for size in 1..=32 {
+            insert(
+                &format!("logBytes{size}"),
+                vec![ParamType::FixedBytes(size)],
+            );
+        }
+
+        Self {
+            signatures,
+            logs: Vec::new(),
+        }
+    }
+}
+
+impl ConsoleLogInspector {
+    /// Returns the captured messages in call order.
+    pub fn logs(&self) -> &[String] {
+        &self.logs
+    }
+
+    /// Consumes the inspector and returns the captured messages.
+    pub fn into_logs(self) -> Vec<String> {
+        self.logs
+    }
+
+    /// Decodes the call data of a `console.log` call.
+    fn decode(&self, input: &[u8]) -> Option<String> {
+        let (selector, data) = input.split_first_chunk::<4>()?;
+        let params = self.signatures.get(selector)?;
+
+        let word = |offset: usize| -> Option<&[u8; 32]> {
+            data.get(offset..offset.checked_add(32)?)?.try_into().ok()
+        };
+        let dynamic = |head: &[u8; 32]| -> Option<&[u8]> {
+            let offset = usize::try_from(U256::from_be_bytes(*head)).ok()?;
+            let len = usize::try_from(U256::from_be_bytes(*word(offset)?)).ok()?;
+            let start = offset.checked_add(32)?;
+            data.get(start..start.checked_add(len)?)
+        };
+
+        let mut values = Vec::with_capacity(params.len());
+        for (i, param) in params.iter().enumerate() {
+            let head = word(i * 32)?;
+            let value = match param {
+                ParamType::Uint => U256::from_be_bytes(*head).to_string(),
+                ParamType::Int => I256::from_raw(U256::from_be_bytes(*head)).to_string(),
+                ParamType::Bool => (*head != [0; 32]).to_string(),
+                ParamType::Address => Address::from_word(B256::from(*head)).to_string(),
+                ParamType::FixedBytes(size) => hex::encode_prefixed(&head[..*size]),
+                ParamType::String => String::from_utf8_lossy(dynamic(head)?).into_owned(),
+                ParamType::Bytes => hex::encode_prefixed(dynamic(head)?),
+            };
+            values.push(value);
+        }
+        Some(values.join(" "))
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for ConsoleLogInspector {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        if inputs.bytecode_address == CONSOLE_ADDRESS {
+            if let Some(message) = self.decode(&inputs.input) {
+                self.logs.push(message);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{CacheDB, EmptyDB},
+        inspector::inspector_handle_register,
+        interpreter::opcode::*,
+        primitives::{AccountInfo, Bytecode, EthereumWiring, TxKind},
+        Evm,
+    };
+
+    #[test]
+    fn captures_log_string_and_uint() {
+        let contract = address!("0000000000000000000000000000000000001000");
+        // CALLDATACOPY(0, 0, CALLDATASIZE), STATICCALL(gas, console, 0, CALLDATASIZE, 0, 0)
+        let mut code = vec![
+            CALLDATASIZE,
+            PUSH1,
+            0,
+            PUSH1,
+            0,
+            CALLDATACOPY,
+            PUSH1,
+            0,
+            PUSH1,
+            0,
+            CALLDATASIZE,
+            PUSH1,
+            0,
+            PUSH20,
+        ];
+        code.extend_from_slice(CONSOLE_ADDRESS.as_slice());
+        code.extend_from_slice(&[GAS, STATICCALL, STOP]);
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+        );
+
+        // log(string,uint256) with "hello" and 42.
+        let mut input = keccak256("log(string,uint256)")[..4].to_vec();
+        input.extend_from_slice(&U256::from(64).to_be_bytes::<32>());
+        input.extend_from_slice(&U256::from(42).to_be_bytes::<32>());
+        input.extend_from_slice(&U256::from(5).to_be_bytes::<32>());
+        input.extend_from_slice(&B256::right_padding_from(b"hello").0);
+
+        let mut evm = Evm::<EthereumWiring<CacheDB<EmptyDB>, ConsoleLogInspector>>::builder()
+            .with_db(db)
+            .with_external_context(ConsoleLogInspector::default())
+            .modify_tx_env(|tx| {
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TxKind::Call(contract);
+                tx.data = input.into();
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        assert!(evm.transact().unwrap().result.is_success());
+
+        assert_eq!(evm.into_context().external.into_logs(), ["hello 42"]);
+    }
+}
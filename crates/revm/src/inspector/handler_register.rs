@@ -31,8 +31,8 @@ impl<EvmWiringT: EvmWiring, INSP: Inspector<EvmWiringT>> GetInspector<EvmWiringT
 /// to use this register with any other register.
 ///
 /// A few instructions handlers are wrapped twice once for `step` and `step_end`
-/// and in case of Logs and Selfdestruct wrapper is wrapped again for the
-/// `log` and `selfdestruct` calls.
+/// and in case of Logs, Selfdestruct, SLOAD and SSTORE wrapper is wrapped again
+/// for the `log`, `selfdestruct`, `sload` and `sstore` calls.
 pub fn inspector_handle_register<
     EvmWiringT: EvmWiring<ExternalContext: GetInspector<EvmWiringT>>,
 >(
@@ -90,6 +90,45 @@ pub fn inspector_handle_register<
         }
     });
 
+    // Register sload function.
+    table.update_boxed(opcode::SLOAD, |prev, interpreter, host| {
+        let Ok(key) = interpreter.stack.peek(0) else {
+            return prev(interpreter, host);
+        };
+        prev(interpreter, host);
+        if interpreter.instruction_result == InstructionResult::Continue {
+            let value = interpreter.stack.peek(0).unwrap();
+            let address = interpreter.contract.target_address;
+            host.external.get_inspector().sload(address, key, value);
+        }
+    });
+
+    // Register sstore function.
+    table.update_boxed(opcode::SSTORE, |prev, interpreter, host| {
+        let (Ok(key), Ok(new)) = (interpreter.stack.peek(0), interpreter.stack.peek(1)) else {
+            return prev(interpreter, host);
+        };
+        let address = interpreter.contract.target_address;
+        // the present value of the slot, if it is already loaded.
+        let present = host
+            .evm
+            .journaled_state
+            .state
+            .get(&address)
+            .and_then(|account| account.storage.get(&key))
+            .map(|slot| slot.present_value);
+        prev(interpreter, host);
+        if interpreter.instruction_result != InstructionResult::Continue {
+            return;
+        }
+        let original = host.evm.journaled_state.state[&address].storage[&key].original_value;
+        // a slot loaded by this SSTORE had its original value.
+        let current = present.unwrap_or(original);
+        host.external
+            .get_inspector()
+            .sstore(address, key, original, current, new);
+    });
+
     // call and create input stack shared between handlers. They are used to share
     // inputs in *_end Inspector calls.
     let call_input_stack = Rc::<RefCell<Vec<_>>>::default();
@@ -111,6 +150,7 @@ pub fn inspector_handle_register<
 
             let mut frame_or_result = prev_handle(ctx, inputs);
             if let Ok(FrameOrResult::Frame(frame)) = &mut frame_or_result {
+                notify_call_depth(ctx);
                 ctx.external
                     .get_inspector()
                     .initialize_interp(frame.interpreter_mut(), &mut ctx.evm)
@@ -132,6 +172,7 @@ pub fn inspector_handle_register<
 
         let mut frame_or_result = prev_handle(ctx, inputs);
         if let Ok(FrameOrResult::Frame(frame)) = &mut frame_or_result {
+            notify_call_depth(ctx);
             ctx.external
                 .get_inspector()
                 .initialize_interp(frame.interpreter_mut(), &mut ctx.evm)
@@ -159,6 +200,7 @@ pub fn inspector_handle_register<
 
         let mut frame_or_result = prev_handle(ctx, inputs);
         if let Ok(FrameOrResult::Frame(frame)) = &mut frame_or_result {
+            notify_call_depth(ctx);
             ctx.external
                 .get_inspector()
                 .initialize_interp(frame.interpreter_mut(), &mut ctx.evm)
@@ -259,13 +301,25 @@ fn inspector_instruction<EvmWiringT>(
         .step_end(interpreter, &mut host.evm);
 }
 
+/// Calls [`Inspector::call_depth_reached`] if the entered frame is at the configured depth.
+fn notify_call_depth<EvmWiringT: EvmWiring<ExternalContext: GetInspector<EvmWiringT>>>(
+    ctx: &mut Context<EvmWiringT>,
+) {
+    let depth = ctx.evm.journaled_state.depth();
+    if ctx.evm.env.cfg.call_depth_warning == Some(depth) {
+        ctx.external
+            .get_inspector()
+            .call_depth_reached(&mut ctx.evm, depth);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         inspectors::NoOpInspector,
         interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
-        primitives::{self, db::EmptyDB, EthereumWiring},
+        primitives::{self, db::EmptyDB, Address, Bytes, EthereumWiring, U256},
         Evm, EvmContext,
     };
 
@@ -399,6 +453,175 @@ mod tests {
         assert!(inspector.call_end);
     }
 
+    #[derive(Default, Debug)]
+    struct StorageInspector {
+        sloads: Vec<(Address, U256, U256)>,
+        sstores: Vec<(Address, U256, U256, U256, U256)>,
+    }
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for StorageInspector {
+        fn sload(&mut self, address: Address, key: U256, value: U256) {
+            self.sloads.push((address, key, value));
+        }
+
+        fn sstore(
+            &mut self,
+            address: Address,
+            key: U256,
+            original: U256,
+            current: U256,
+            new: U256,
+        ) {
+            self.sstores.push((address, key, original, current, new));
+        }
+    }
+
+    #[test]
+    fn test_inspector_storage_hooks() {
+        use crate::{
+            db::{CacheDB, EmptyDB},
+            interpreter::opcode::*,
+            primitives::{address, AccountInfo, Bytecode, TxKind},
+        };
+
+        let contract = address!("0000000000000000000000000000000000001000");
+        // SSTORE(1, 7), SSTORE(1, 7), SSTORE(1, 9), SLOAD(1)
+        let code = vec![
+            PUSH1, 7, PUSH1, 1, SSTORE, PUSH1, 7, PUSH1, 1, SSTORE, PUSH1, 9, PUSH1, 1, SSTORE,
+            PUSH1, 1, SLOAD, STOP,
+        ];
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+        );
+        db.insert_account_storage(contract, U256::from(1), U256::from(5))
+            .unwrap();
+
+        let mut evm = Evm::<EthereumWiring<CacheDB<EmptyDB>, StorageInspector>>::builder()
+            .with_db(db)
+            .with_external_context(StorageInspector::default())
+            .modify_tx_env(|tx| {
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TxKind::Call(contract);
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let inspector = evm.into_context().external;
+        let [one, five, seven, nine] = [1, 5, 7, 9].map(U256::from);
+        assert_eq!(
+            inspector.sstores,
+            vec![
+                (contract, one, five, five, seven),
+                // storing the same value again is not journaled.
+                (contract, one, five, seven, seven),
+                (contract, one, five, seven, nine)
+            ]
+        );
+        assert_eq!(inspector.sloads, vec![(contract, one, nine)]);
+    }
+
+    #[derive(Default, Debug)]
+    struct DepthInspector {
+        reached: Vec<u64>,
+    }
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for DepthInspector {
+        fn call_depth_reached(&mut self, _context: &mut EvmContext<EvmWiringT>, depth: u64) {
+            self.reached.push(depth);
+        }
+    }
+
+    #[test]
+    fn test_call_depth_reached() {
+        use crate::{
+            db::{CacheDB, EmptyDB},
+            primitives::TxKind,
+            test_utils::call_chain,
+        };
+
+        let (contracts, db) = call_chain(5);
+
+        let mut evm = Evm::<EthereumWiring<CacheDB<EmptyDB>, DepthInspector>>::builder()
+            .with_db(db)
+            .with_external_context(DepthInspector::default())
+            .modify_cfg_env(|cfg| cfg.call_depth_warning = Some(3))
+            .modify_tx_env(|tx| tx.transact_to = TxKind::Call(contracts[0]))
+            .append_handler_register(inspector_handle_register)
+            .build();
+        assert!(evm.transact().unwrap().result.is_success());
+
+        // reached once, by the third contract, without halting.
+        assert_eq!(evm.into_context().external.reached, vec![3]);
+    }
+
+    #[derive(Default, Debug)]
+    struct CreateInspector {
+        init_codes: Vec<Bytes>,
+        created: Vec<(Option<Address>, usize)>,
+    }
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for CreateInspector {
+        fn create(
+            &mut self,
+            _context: &mut EvmContext<EvmWiringT>,
+            inputs: &mut CreateInputs,
+        ) -> Option<CreateOutcome> {
+            self.init_codes.push(inputs.init_code.clone());
+            None
+        }
+
+        fn create_end(
+            &mut self,
+            _context: &mut EvmContext<EvmWiringT>,
+            _inputs: &CreateInputs,
+            outcome: CreateOutcome,
+        ) -> CreateOutcome {
+            self.created.push((outcome.address, outcome.output().len()));
+            outcome
+        }
+    }
+
+    #[test]
+    fn test_create2_hooks() {
+        use crate::{
+            db::{CacheDB, EmptyDB},
+            interpreter::opcode::*,
+            primitives::{address, AccountInfo, Bytecode, TxKind, B256},
+        };
+
+        let factory = address!("0000000000000000000000000000000000001000");
+        // RETURN(0, 3), deploying three zero bytes.
+        let init_code = [PUSH1, 3, PUSH1, 0, RETURN];
+        // MSTORE(0, init_code), CREATE2(0, 27, 5, 0)
+        let mut code = vec![PUSH5];
+        code.extend_from_slice(&init_code);
+        code.extend_from_slice(&[
+            PUSH1, 0, MSTORE, PUSH1, 0, PUSH1, 5, PUSH1, 27, PUSH1, 0, CREATE2, STOP,
+        ]);
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            factory,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+        );
+
+        let mut evm = Evm::<EthereumWiring<CacheDB<EmptyDB>, CreateInspector>>::builder()
+            .with_db(db)
+            .with_external_context(CreateInspector::default())
+            .modify_tx_env(|tx| tx.transact_to = TxKind::Call(factory))
+            .append_handler_register(inspector_handle_register)
+            .build();
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let inspector = evm.into_context().external;
+        assert_eq!(inspector.init_codes, vec![Bytes::from(init_code)]);
+        let expected = factory.create2_from_code(B256::ZERO, init_code);
+        assert_eq!(inspector.created, vec![(Some(expected), 3)]);
+    }
+
     #[test]
     fn test_inspector_reg() {
         let mut noop = NoOpInspector;
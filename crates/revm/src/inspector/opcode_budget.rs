@@ -0,0 +1,77 @@
+//! OpcodeBudgetInspector. Halts execution after a fixed number of opcodes.
+
+use crate::{
+    interpreter::{InstructionResult, Interpreter},
+    EvmContext, EvmWiring, Inspector,
+};
+
+/// [Inspector] that halts execution with [`InstructionResult::OpcodeBudgetExceeded`] once
+/// the configured number of opcodes has been executed, independently of gas.
+///
+/// The budget is shared by all call frames of the inspected execution.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OpcodeBudgetInspector {
+    remaining: u64,
+}
+
+impl OpcodeBudgetInspector {
+    /// Creates a new inspector that allows at most `max_steps` opcodes to be executed.
+    pub fn new(max_steps: u64) -> Self {
+        Self {
+            remaining: max_steps,
+        }
+    }
+
+    /// Returns the number of opcodes that can still be executed.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for OpcodeBudgetInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<EvmWiringT>) {
+        if self.remaining == 0 {
+            interp.instruction_result = InstructionResult::OpcodeBudgetExceeded;
+            return;
+        }
+        self.remaining -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector::inspector_handle_register,
+        interpreter::opcode,
+        primitives::{address, Bytecode, EthereumWiring, ExecutionResult, HaltReason, TxKind},
+        Evm,
+    };
+
+    #[test]
+    fn halts_once_budget_is_exhausted() {
+        let bytecode = Bytecode::new_raw(vec![opcode::JUMPDEST; 100].into());
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, OpcodeBudgetInspector>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_external_context(OpcodeBudgetInspector::new(5))
+            .modify_tx_env(|tx| {
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let result = evm.transact().unwrap().result;
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::OpcodeBudgetExceeded,
+                ..
+            }
+        ));
+        assert_eq!(evm.context.external.remaining(), 0);
+    }
+}
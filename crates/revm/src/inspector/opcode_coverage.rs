@@ -0,0 +1,94 @@
+//! OpcodeCoverageInspector. Records which opcodes have been executed.
+
+use crate::{interpreter::Interpreter, EvmContext, EvmWiring, Inspector};
+
+/// [Inspector] that records which of the 256 opcodes have been executed at least once.
+///
+/// Coverage accumulates over all transactions the inspector is used for, until
+/// [`OpcodeCoverageInspector::reset`] is called.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpcodeCoverageInspector {
+    covered: [bool; 256],
+}
+
+impl Default for OpcodeCoverageInspector {
+    fn default() -> Self {
+        Self {
+            covered: [false; 256],
+        }
+    }
+}
+
+impl OpcodeCoverageInspector {
+    /// Returns the coverage bitmap, indexed by opcode.
+    pub fn covered(&self) -> [bool; 256] {
+        self.covered
+    }
+
+    /// Returns `true` if `opcode` has been executed.
+    pub fn is_covered(&self, opcode: u8) -> bool {
+        self.covered[opcode as usize]
+    }
+
+    /// Clears the recorded coverage.
+    pub fn reset(&mut self) {
+        self.covered = [false; 256];
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for OpcodeCoverageInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<EvmWiringT>) {
+        self.covered[interp.current_opcode() as usize] = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{CacheDB, EmptyDB},
+        inspector::inspector_handle_register,
+        interpreter::opcode::*,
+        primitives::{address, AccountInfo, Bytecode, EthereumWiring, TxKind},
+        Evm,
+    };
+
+    #[test]
+    fn coverage_accumulates_over_transactions() {
+        let a = address!("0000000000000000000000000000000000001000");
+        let b = address!("0000000000000000000000000000000000002000");
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (address, code) in [
+            (a, vec![PUSH1, 1, PUSH1, 2, ADD, STOP]),
+            (b, vec![PUSH1, 1, PUSH1, 2, MUL, POP, STOP]),
+        ] {
+            db.insert_account_info(
+                address,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+            );
+        }
+
+        let mut evm = Evm::<EthereumWiring<CacheDB<EmptyDB>, OpcodeCoverageInspector>>::builder()
+            .with_db(db)
+            .with_external_context(OpcodeCoverageInspector::default())
+            .modify_tx_env(|tx| {
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TxKind::Call(a);
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        assert!(evm.transact().unwrap().result.is_success());
+        evm.tx_mut().transact_to = TxKind::Call(b);
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let coverage = &mut evm.context.external;
+        let covered = coverage.covered();
+        for opcode in [PUSH1, ADD, MUL, POP, STOP] {
+            assert!(covered[opcode as usize]);
+        }
+        assert_eq!(covered.iter().filter(|covered| **covered).count(), 5);
+
+        coverage.reset();
+        assert!(!coverage.is_covered(PUSH1));
+    }
+}
@@ -0,0 +1,181 @@
+//! OutOfGasInspector. Injects an out of gas fault at a chosen point of execution.
+
+use crate::{
+    interpreter::{
+        CallInputs, CallOutcome, Gas, InstructionResult, Interpreter, InterpreterResult,
+    },
+    primitives::Bytes,
+    EvmContext, EvmWiring, Inspector,
+};
+
+/// Point at which [`OutOfGasInspector`] injects the fault.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FaultPoint {
+    /// Zero-based index of the opcode, counted across all frames.
+    Step(u64),
+    /// Zero-based index of the call, the top level call is `0`.
+    Call(u64),
+}
+
+/// [Inspector] that makes a chosen opcode or call run out of gas, for testing error handling
+/// without crafting exact gas limits.
+///
+/// An injected opcode fault halts its frame with [`InstructionResult::OutOfGas`] instead of
+/// executing the opcode, and consumes the remaining gas of the frame like a real out of
+/// gas. An injected call fault returns [`InstructionResult::OutOfGas`] for the call without
+/// executing it. The fault is injected at most once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutOfGasInspector {
+    point: FaultPoint,
+    /// Opcodes or calls seen so far.
+    count: u64,
+    injected: bool,
+}
+
+impl OutOfGasInspector {
+    /// Creates an inspector that fails the opcode with index `step` of the execution.
+    ///
+    /// Opcodes are counted from zero across all call frames.
+    pub fn at_step(step: u64) -> Self {
+        Self::new(FaultPoint::Step(step))
+    }
+
+    /// Creates an inspector that fails the call with index `call` of the execution.
+    ///
+    /// Calls are counted from zero in the order they start, the top level call is `0`.
+    pub fn at_call(call: u64) -> Self {
+        Self::new(FaultPoint::Call(call))
+    }
+
+    fn new(point: FaultPoint) -> Self {
+        Self {
+            point,
+            count: 0,
+            injected: false,
+        }
+    }
+
+    /// Returns `true` if the fault has been injected.
+    pub fn is_injected(&self) -> bool {
+        self.injected
+    }
+
+    /// Counts an opcode or call and returns `true` if the fault is to be injected at it.
+    fn inject(&mut self, index: u64) -> bool {
+        let inject = !self.injected && self.count == index;
+        self.count += 1;
+        self.injected |= inject;
+        inject
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for OutOfGasInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<EvmWiringT>) {
+        if let FaultPoint::Step(step) = self.point {
+            if self.inject(step) {
+                interp.gas.spend_all();
+                interp.instruction_result = InstructionResult::OutOfGas;
+            }
+        }
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        let FaultPoint::Call(call) = self.point else {
+            return None;
+        };
+        self.inject(call).then(|| {
+            CallOutcome::new(
+                InterpreterResult::new(
+                    InstructionResult::OutOfGas,
+                    Bytes::new(),
+                    Gas::new_spent(inputs.gas_limit),
+                ),
+                inputs.return_memory_offset.clone(),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{CacheDB, EmptyDB},
+        inspector::inspector_handle_register,
+        interpreter::opcode::*,
+        primitives::{
+            address, AccountInfo, Bytecode, EthereumWiring, ExecutionResult, HaltReason,
+            OutOfGasError, ResultAndState, TxKind, U256,
+        },
+        Evm,
+    };
+
+    /// Runs a call that stores whether its sub call succeeded.
+    fn transact(
+        inspector: OutOfGasInspector,
+    ) -> (ExecutionResult<HaltReason>, U256, OutOfGasInspector) {
+        let a = address!("0000000000000000000000000000000000001000");
+        let b = address!("0000000000000000000000000000000000002000");
+
+        // SSTORE(0, STATICCALL(10000, b, 0, 0, 0, 0))
+        let mut code_a = vec![PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH20];
+        code_a.extend_from_slice(b.as_slice());
+        code_a.extend_from_slice(&[PUSH2, 0x27, 0x10, STATICCALL, PUSH1, 0, SSTORE, STOP]);
+        let code_b = vec![PUSH1, 1, PUSH1, 2, PUSH1, 3, STOP];
+
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (address, code) in [(a, code_a), (b, code_b)] {
+            db.insert_account_info(
+                address,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+            );
+        }
+
+        let mut evm = Evm::<EthereumWiring<CacheDB<EmptyDB>, OutOfGasInspector>>::builder()
+            .with_db(db)
+            .with_external_context(inspector)
+            .modify_tx_env(|tx| {
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TxKind::Call(a);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        let stored = state[&a]
+            .storage
+            .get(&U256::ZERO)
+            .map_or(U256::ZERO, |slot| slot.present_value);
+        (result, stored, evm.into_context().external)
+    }
+
+    #[test]
+    fn injects_out_of_gas_at_third_opcode() {
+        let (result, _, inspector) = transact(OutOfGasInspector::at_step(2));
+        assert!(inspector.is_injected());
+        assert_eq!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::OutOfGas(OutOfGasError::Basic),
+                gas_used: 100_000,
+            }
+        );
+    }
+
+    #[test]
+    fn injects_out_of_gas_into_sub_call() {
+        let (result, stored, inspector) = transact(OutOfGasInspector::at_call(1));
+        assert!(inspector.is_injected());
+        assert!(result.is_success());
+        assert_eq!(stored, U256::ZERO);
+
+        let (result, stored, inspector) = transact(OutOfGasInspector::at_call(2));
+        assert!(!inspector.is_injected());
+        assert!(result.is_success());
+        assert_eq!(stored, U256::from(1));
+    }
+}
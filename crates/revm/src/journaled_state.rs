@@ -3,9 +3,9 @@ use revm_interpreter::Eip7702CodeLoad;
 use crate::{
     interpreter::{AccountLoad, InstructionResult, SStoreResult, SelfDestructResult, StateLoad},
     primitives::{
-        db::Database, hash_map::Entry, Account, Address, Bytecode, EvmState, EvmStorageSlot,
-        HashMap, HashSet, Log, SpecId, SpecId::*, TransientStorage, B256, KECCAK_EMPTY,
-        PRECOMPILE3, U256,
+        db::Database, hash_map::Entry, AccessList, AccessListItem, Account, Address, Bytecode,
+        EvmState, EvmStorageSlot, HashMap, HashSet, Log, SpecId, SpecId::*, TransientStorage,
+        TxKind, B256, KECCAK_EMPTY, PRECOMPILE3, U256,
     },
 };
 use core::mem;
@@ -23,7 +23,10 @@ pub struct JournaledState {
     ///
     /// See [EIP-1153](https://eips.ethereum.org/EIPS/eip-1153).
     pub transient_storage: TransientStorage,
-    /// Emitted logs.
+    /// Emitted logs, in execution order across all call frames.
+    ///
+    /// Logs of reverted frames are removed by [`JournaledState::checkpoint_revert`], so the
+    /// remaining logs are the ones of the final receipt, in the order they were emitted.
     pub logs: Vec<Log>,
     /// The current call stack depth.
     pub depth: usize,
@@ -101,6 +104,46 @@ impl JournaledState {
         }
     }
 
+    /// Returns the addresses of accounts touched in the journal.
+    ///
+    /// Touching is distinct from warming: an account is touched by a state-relevant interaction
+    /// such as a value transfer (even of zero value), while merely loading an account or reading
+    /// its storage only warms it. Touches that were reverted are not included, and neither are
+    /// accounts marked as touched outside of the journal (e.g. the caller charged for gas in
+    /// pre-execution).
+    pub fn touched_accounts(&self) -> impl Iterator<Item = Address> + '_ {
+        self.journal
+            .iter()
+            .flatten()
+            .filter_map(|entry| match entry {
+                JournalEntry::AccountTouched { address } => Some(*address),
+                _ => None,
+            })
+    }
+
+    /// Returns the value `address` received from other accounts, through balance transfers and
+    /// self-destructs. Value received in reverted frames is not included.
+    pub fn value_received(&self, address: Address) -> U256 {
+        self.journal
+            .iter()
+            .flatten()
+            .filter_map(|entry| match entry {
+                JournalEntry::BalanceTransfer { from, to, balance }
+                    if *to == address && *from != address =>
+                {
+                    Some(*balance)
+                }
+                JournalEntry::AccountDestroyed {
+                    address: destroyed,
+                    target,
+                    had_balance,
+                    ..
+                } if *target == address && *destroyed != address => Some(*had_balance),
+                _ => None,
+            })
+            .fold(U256::ZERO, |sum, value| sum.saturating_add(value))
+    }
+
     /// Clears the JournaledState. Preserving only the spec.
     pub fn clear(&mut self) {
         let spec = self.spec;
@@ -146,6 +189,56 @@ impl JournaledState {
             .expect("Account expected to be loaded") // Always assume that acc is already loaded
     }
 
+    /// Returns the access list of a transaction from `caller` to `target`, built from the
+    /// accounts and storage slots loaded so far and sorted by address and key.
+    ///
+    /// Addresses that are warm without an access list are left out unless storage slots of
+    /// them were loaded. These are the caller, the called address and the
+    /// [`Self::warm_preloaded_addresses`]: the precompiles of the active spec and, from
+    /// Shanghai on, the coinbase. The address created by a create transaction is listed.
+    pub fn access_list(&self, caller: Address, target: TxKind) -> AccessList {
+        let is_warm = |address: &Address| {
+            *address == caller
+                || target == TxKind::Call(*address)
+                || self.warm_preloaded_addresses.contains(address)
+        };
+        let mut items: Vec<_> = self
+            .state
+            .iter()
+            .filter(|(address, account)| !account.storage.is_empty() || !is_warm(address))
+            .map(|(address, account)| {
+                let mut storage_keys: Vec<_> =
+                    account.storage.keys().map(|key| B256::from(*key)).collect();
+                storage_keys.sort_unstable();
+                AccessListItem {
+                    address: *address,
+                    storage_keys,
+                }
+            })
+            .collect();
+        items.sort_unstable_by_key(|item| item.address);
+        AccessList(items)
+    }
+
+    /// Returns the loaded storage slots whose value is unchanged, by account.
+    ///
+    /// These are the slots that were read but not written, or written back to their original
+    /// value. Slots warmed by the access list count as read.
+    pub fn read_only_slots(&self) -> HashMap<Address, HashSet<U256>> {
+        self.state
+            .iter()
+            .filter_map(|(address, account)| {
+                let slots: HashSet<_> = account
+                    .storage
+                    .iter()
+                    .filter(|(_, slot)| !slot.is_changed())
+                    .map(|(key, _)| *key)
+                    .collect();
+                (!slots.is_empty()).then_some((*address, slots))
+            })
+            .collect()
+    }
+
     /// Returns call depth.
     #[inline]
     pub fn depth(&self) -> u64 {
@@ -444,6 +537,8 @@ impl JournaledState {
     }
 
     /// Reverts all changes to state until given checkpoint.
+    ///
+    /// Logs emitted since the checkpoint are dropped, earlier logs keep their order.
     #[inline]
     pub fn checkpoint_revert(&mut self, checkpoint: JournalCheckpoint) {
         let is_spurious_dragon_enabled = SpecId::enabled(self.spec, SPURIOUS_DRAGON);
@@ -556,7 +651,12 @@ impl JournaledState {
     ) -> Result<&mut Account, DB::Error> {
         // load or get account.
         let account = match self.state.entry(address) {
-            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Occupied(entry) => {
+                // account can be preloaded cold, see `EvmContext::with_state_override`.
+                let account = entry.into_mut();
+                account.mark_warm();
+                account
+            }
             Entry::Vacant(vac) => vac.insert(
                 db.basic(address)?
                     .map(|i| i.into())
@@ -565,9 +665,14 @@ impl JournaledState {
         };
         // preload storages.
         for storage_key in storage_keys.into_iter() {
-            if let Entry::Vacant(entry) = account.storage.entry(storage_key) {
-                let storage = db.storage(address, storage_key)?;
-                entry.insert(EvmStorageSlot::new(storage));
+            match account.storage.entry(storage_key) {
+                Entry::Occupied(entry) => {
+                    entry.into_mut().mark_warm();
+                }
+                Entry::Vacant(entry) => {
+                    let storage = db.storage(address, storage_key)?;
+                    entry.insert(EvmStorageSlot::new(storage));
+                }
             }
         }
         Ok(account)
@@ -583,7 +688,8 @@ impl JournaledState {
         let load = match self.state.entry(address) {
             Entry::Occupied(entry) => {
                 let account = entry.into_mut();
-                let is_cold = account.mark_warm();
+                let is_cold =
+                    account.mark_warm() && !self.warm_preloaded_addresses.contains(&address);
                 StateLoad {
                     data: account,
                     is_cold,
@@ -817,7 +923,7 @@ impl JournaledState {
         }
     }
 
-    /// push log into subroutine
+    /// Appends a log to [`JournaledState::logs`].
     #[inline]
     pub fn log(&mut self, log: Log) {
         self.logs.push(log);
@@ -897,3 +1003,219 @@ pub struct JournalCheckpoint {
     log_i: usize,
     journal_i: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{CacheDB, EmptyDB},
+        inspector::inspector_handle_register,
+        interpreter::{opcode::*, CallInputs, CallOutcome},
+        primitives::{address, AccountInfo, EthereumWiring, HaltReason, ResultAndState, B256},
+        test_utils::{db_with_code, deploy_and_call, evm_with_code, CALLER, CONTRACT},
+        Evm, EvmContext, EvmWiring, Inspector,
+    };
+    use core::fmt::Debug;
+
+    #[test]
+    fn test_touched_accounts() {
+        let from = Address::with_last_byte(1);
+        let to = Address::with_last_byte(2);
+        let read_only = Address::with_last_byte(3);
+
+        let mut db = EmptyDB::default();
+        let mut journaled_state = JournaledState::new(SpecId::CANCUN, HashSet::new());
+
+        // A zero value transfer touches both accounts.
+        assert_eq!(
+            journaled_state.transfer(&from, &to, U256::ZERO, &mut db),
+            Ok(None)
+        );
+
+        // Loading an account and reading its storage does not touch it.
+        journaled_state.load_account(read_only, &mut db).unwrap();
+        journaled_state
+            .sload(read_only, U256::ZERO, &mut db)
+            .unwrap();
+
+        let touched: Vec<_> = journaled_state.touched_accounts().collect();
+        assert_eq!(touched, vec![from, to]);
+    }
+
+    #[test]
+    fn reverted_subcall_rolls_back_transient_storage() {
+        // The contract calls itself with non-empty calldata. The subcall overwrites the
+        // transient slot set by the parent and reverts; the parent returns the slot value.
+        let code = [
+            CALLDATASIZE,
+            PUSH1,
+            0x22,
+            JUMPI,
+            // parent frame
+            PUSH1,
+            0x01,
+            PUSH1,
+            0x00,
+            TSTORE,
+            PUSH1,
+            0x00,
+            PUSH1,
+            0x00,
+            PUSH1,
+            0x01,
+            PUSH1,
+            0x00,
+            PUSH1,
+            0x00,
+            ADDRESS,
+            GAS,
+            CALL,
+            POP,
+            PUSH1,
+            0x00,
+            TLOAD,
+            PUSH1,
+            0x00,
+            MSTORE,
+            PUSH1,
+            0x20,
+            PUSH1,
+            0x00,
+            RETURN,
+            // subcall
+            JUMPDEST,
+            PUSH1,
+            0x02,
+            PUSH1,
+            0x00,
+            TSTORE,
+            PUSH1,
+            0x00,
+            PUSH1,
+            0x00,
+            REVERT,
+        ];
+
+        let result = deploy_and_call(&code, SpecId::CANCUN).result;
+        assert_eq!(
+            result.output().map(|out| U256::from_be_slice(out)),
+            Some(U256::from(1))
+        );
+    }
+
+    #[test]
+    fn logs_of_reverted_frame_are_removed_in_order() {
+        let b = address!("0000000000000000000000000000000000002000");
+        // LOG1(topic 1), CALL(gas, b, 0, 0, 0, 0, 0), LOG1(topic 2)
+        let mut code = vec![PUSH1, 1, PUSH1, 0, PUSH1, 0, LOG1];
+        code.extend_from_slice(&[PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH20]);
+        code.extend_from_slice(b.as_slice());
+        code.extend_from_slice(&[GAS, CALL, POP, PUSH1, 2, PUSH1, 0, PUSH1, 0, LOG1, STOP]);
+        // LOG1(topic 3), REVERT(0, 0)
+        let code_b = vec![
+            PUSH1, 3, PUSH1, 0, PUSH1, 0, LOG1, PUSH1, 0, PUSH1, 0, REVERT,
+        ];
+
+        let mut evm = evm_with_code(&code, SpecId::LATEST);
+        evm.db_mut().insert_account_info(
+            b,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(code_b.into())),
+        );
+        let result = evm.transact().unwrap().result;
+        assert!(result.is_success());
+
+        let topics: Vec<_> = result
+            .logs()
+            .iter()
+            .map(|log| (log.address, log.topics().to_vec()))
+            .collect();
+        assert_eq!(
+            topics,
+            [
+                (CONTRACT, vec![B256::with_last_byte(1)]),
+                (CONTRACT, vec![B256::with_last_byte(2)])
+            ]
+        );
+    }
+
+    /// Records a view of the journaled state at the end of each call.
+    #[derive(Debug)]
+    struct JournalRecorder<T> {
+        view: fn(&JournaledState) -> T,
+        recorded: Option<T>,
+    }
+
+    impl<EvmWiringT: EvmWiring, T> Inspector<EvmWiringT> for JournalRecorder<T> {
+        fn call_end(
+            &mut self,
+            context: &mut EvmContext<EvmWiringT>,
+            _inputs: &CallInputs,
+            outcome: CallOutcome,
+        ) -> CallOutcome {
+            self.recorded = Some((self.view)(&context.journaled_state));
+            outcome
+        }
+    }
+
+    /// Calls `code` deployed at [`CONTRACT`] and returns the `view` of the journaled state at
+    /// the end of the top level call.
+    fn view_at_call_end<T: Debug>(
+        code: &[u8],
+        view: fn(&JournaledState) -> T,
+    ) -> (ResultAndState<HaltReason>, T) {
+        let mut evm = Evm::<EthereumWiring<CacheDB<EmptyDB>, JournalRecorder<T>>>::builder()
+            .with_db(db_with_code([(CONTRACT, code)]))
+            .with_external_context(JournalRecorder {
+                view,
+                recorded: None,
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = CALLER;
+                tx.transact_to = TxKind::Call(CONTRACT);
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        let out = evm.transact().unwrap();
+        (out, evm.into_context().external.recorded.unwrap())
+    }
+
+    #[test]
+    fn read_only_slots_exclude_written_slots() {
+        // SSTORE(2, SLOAD(1) + 1)
+        let code = [PUSH1, 1, SLOAD, PUSH1, 1, ADD, PUSH1, 2, SSTORE, STOP];
+        let (out, read_only) = view_at_call_end(&code, JournaledState::read_only_slots);
+        assert!(out.result.is_success());
+        assert!(out.state[&CONTRACT].storage[&U256::from(2)].is_changed());
+
+        assert_eq!(read_only.len(), 1);
+        assert_eq!(read_only[&CONTRACT], HashSet::from_iter([U256::from(1)]));
+    }
+
+    #[test]
+    fn access_list_excludes_warm_addresses() {
+        // SLOAD(5), STATICCALL(gas, ECRECOVER, 0, 0, 0, 0), BALANCE(0x2000)
+        let code = [
+            PUSH1, 5, SLOAD, POP, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 1, GAS,
+            STATICCALL, POP, PUSH2, 0x20, 0, BALANCE, STOP,
+        ];
+        let (out, access_list) = view_at_call_end(&code, |journal| {
+            journal.access_list(CALLER, TxKind::Call(CONTRACT))
+        });
+        assert!(out.result.is_success());
+
+        // The caller and ECRECOVER are warm, the target is listed for its slot.
+        assert_eq!(
+            access_list.0,
+            vec![
+                AccessListItem {
+                    address: CONTRACT,
+                    storage_keys: vec![B256::with_last_byte(5)],
+                },
+                AccessListItem {
+                    address: address!("0000000000000000000000000000000000002000"),
+                    storage_keys: vec![],
+                },
+            ]
+        );
+    }
+}
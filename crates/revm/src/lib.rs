@@ -21,14 +21,18 @@ mod frame;
 pub mod handler;
 mod inspector;
 mod journaled_state;
+#[cfg(feature = "parallel")]
+pub mod parallel;
 
 // Export items.
 
 pub use builder::EvmBuilder;
+#[cfg(feature = "std")]
+pub use context::PrecompileMetricsHook;
 pub use context::{
-    Context, ContextPrecompile, ContextPrecompiles, ContextStatefulPrecompile,
+    AccountOverride, Context, ContextPrecompile, ContextPrecompiles, ContextStatefulPrecompile,
     ContextStatefulPrecompileArc, ContextStatefulPrecompileBox, ContextStatefulPrecompileMut,
-    ContextWithEvmWiring, EvmContext, InnerEvmContext,
+    ContextWithEvmWiring, EvmContext, InnerEvmContext, StateOverride,
 };
 pub use db::{
     CacheState, DBBox, State, StateBuilder, StateDBBox, TransitionAccount, TransitionState,
@@ -0,0 +1,366 @@
+//! Optimistic parallel execution of the transactions of a block.
+//!
+//! This is a scaffold for research into parallel execution, not a production block executor.
+
+use crate::{
+    db::CacheDB,
+    primitives::{
+        Address, BlockEnv, EVMError, EthereumWiring, HaltReason, HashSet, InvalidTransaction,
+        ResultAndState, SpecId, TxEnv, U256,
+    },
+    DatabaseCommit, DatabaseRef, Evm,
+};
+use core::fmt;
+use std::{thread, vec::Vec};
+
+/// Accounts and storage slots read or written by a transaction.
+#[derive(Debug, Default)]
+struct AccessSet {
+    accounts: HashSet<Address>,
+    slots: HashSet<(Address, U256)>,
+}
+
+impl AccessSet {
+    /// Returns `true` if `reads` contains an account or slot of `self`.
+    fn conflicts_with(&self, reads: &AccessSet) -> bool {
+        reads
+            .accounts
+            .iter()
+            .any(|address| self.accounts.contains(address))
+            || reads.slots.iter().any(|slot| self.slots.contains(slot))
+    }
+}
+
+/// Error of [`execute_parallel`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockError<DBError> {
+    /// A transaction failed to execute.
+    Evm(EVMError<DBError, InvalidTransaction>),
+    /// The gas limit of the transaction at `index` exceeds the gas left in the block by the
+    /// transactions before it.
+    ///
+    /// `included` holds the results of the transactions before it, which fit in the block.
+    GasLimitReached {
+        index: usize,
+        gas_limit: u64,
+        remaining: u64,
+        included: Vec<ResultAndState<HaltReason>>,
+    },
+}
+
+impl<DBError> From<EVMError<DBError, InvalidTransaction>> for BlockError<DBError> {
+    fn from(value: EVMError<DBError, InvalidTransaction>) -> Self {
+        Self::Evm(value)
+    }
+}
+
+impl<DBError: fmt::Display> fmt::Display for BlockError<DBError> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Evm(err) => err.fmt(f),
+            Self::GasLimitReached {
+                index,
+                gas_limit,
+                remaining,
+                ..
+            } => write!(
+                f,
+                "gas limit {gas_limit} of transaction {index} exceeds the remaining block gas {remaining}"
+            ),
+        }
+    }
+}
+
+impl<DBError: std::error::Error + 'static> std::error::Error for BlockError<DBError> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Evm(err) => Some(err),
+            Self::GasLimitReached { .. } => None,
+        }
+    }
+}
+
+/// Executes `txs` in `block` on top of `db` and returns their results in order.
+///
+/// All transactions are first executed concurrently, on at most
+/// [`thread::available_parallelism`] threads, each against its own [`CacheDB`] over `db`.
+/// The results are then validated in order: a result is kept if the transaction
+/// read no account or slot written by an earlier transaction, otherwise the transaction is
+/// executed again on top of the state of the earlier transactions. The results are the
+/// same as of serial execution.
+///
+/// Conflicts are detected on whole accounts for balance, nonce and code, and on single
+/// slots for storage. Note that transactions paying fees to the same coinbase all
+/// conflict with each other.
+///
+/// The gas used by the transactions is accumulated against `block.gas_limit`. The block is
+/// rejected with [`BlockError::GasLimitReached`], holding the results of the transactions
+/// that fit, if the gas limit of a transaction exceeds the gas left by the transactions
+/// before it.
+pub fn execute_parallel<DB>(
+    block: &BlockEnv,
+    spec_id: SpecId,
+    txs: &[TxEnv],
+    db: &DB,
+) -> Result<Vec<ResultAndState<HaltReason>>, BlockError<DB::Error>>
+where
+    DB: DatabaseRef + Sync,
+    DB::Error: Send,
+{
+    let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+    execute_on_threads(block, spec_id, txs, db, threads)
+}
+
+/// [`execute_parallel`] on at most `threads` threads, each executing a contiguous chunk of the
+/// transactions optimistically.
+fn execute_on_threads<DB>(
+    block: &BlockEnv,
+    spec_id: SpecId,
+    txs: &[TxEnv],
+    db: &DB,
+    threads: usize,
+) -> Result<Vec<ResultAndState<HaltReason>>, BlockError<DB::Error>>
+where
+    DB: DatabaseRef + Sync,
+    DB::Error: Send,
+{
+    let chunk_size = txs.len().div_ceil(threads).max(1);
+    let optimistic: Vec<_> = thread::scope(|scope| {
+        let handles: Vec<_> = txs
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|tx| execute(block, spec_id, tx, &mut CacheDB::new(db)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("execution thread panicked"))
+            .collect()
+    });
+
+    let mut state = CacheDB::new(db);
+    let mut writes = AccessSet::default();
+    let mut results = Vec::with_capacity(txs.len());
+    let mut cumulative_gas_used = 0u64;
+    for (index, (tx, optimistic)) in txs.iter().zip(optimistic).enumerate() {
+        let remaining = block
+            .gas_limit
+            .saturating_to::<u64>()
+            .saturating_sub(cumulative_gas_used);
+        if tx.gas_limit > remaining {
+            return Err(BlockError::GasLimitReached {
+                index,
+                gas_limit: tx.gas_limit,
+                remaining,
+                included: results,
+            });
+        }
+        let (out, tx_writes) = match optimistic {
+            Ok((out, tx_writes)) if !writes.conflicts_with(&reads(&out)) => (out, tx_writes),
+            // optimistic errors are retried, the transaction may depend on earlier ones.
+            _ => execute(block, spec_id, tx, &mut state)?,
+        };
+        cumulative_gas_used += out.result.gas_used();
+        state.commit(out.state.clone());
+        writes.accounts.extend(tx_writes.accounts);
+        writes.slots.extend(tx_writes.slots);
+        results.push(out);
+    }
+    Ok(results)
+}
+
+/// Executes `tx` against `db` without committing and returns the result and its writes.
+fn execute<ExtDB: DatabaseRef>(
+    block: &BlockEnv,
+    spec_id: SpecId,
+    tx: &TxEnv,
+    db: &mut CacheDB<ExtDB>,
+) -> Result<(ResultAndState<HaltReason>, AccessSet), EVMError<ExtDB::Error, InvalidTransaction>> {
+    let out = Evm::<EthereumWiring<&mut CacheDB<ExtDB>, ()>>::builder()
+        .with_db(&mut *db)
+        .with_default_ext_ctx()
+        .modify_block_env(|b| b.clone_from(block))
+        .modify_tx_env(|t| t.clone_from(tx))
+        .with_spec_id(spec_id)
+        .build()
+        .transact()?;
+
+    // the cache holds the accounts as they were before the transaction.
+    let mut writes = AccessSet::default();
+    for (address, account) in &out.state {
+        let info_changed = match db.accounts.get(address) {
+            Some(before) => {
+                before.info.balance != account.info.balance
+                    || before.info.nonce != account.info.nonce
+                    || before.info.code_hash != account.info.code_hash
+            }
+            None => true,
+        };
+        if info_changed || account.is_created() || account.is_selfdestructed() {
+            writes.accounts.insert(*address);
+        }
+        writes.slots.extend(
+            account
+                .changed_storage_slots()
+                .map(|(slot, _)| (*address, *slot)),
+        );
+    }
+    Ok((out, writes))
+}
+
+/// Returns the accounts and slots loaded by a transaction.
+fn reads(out: &ResultAndState<HaltReason>) -> AccessSet {
+    AccessSet {
+        accounts: out.state.keys().copied().collect(),
+        slots: out
+            .state
+            .iter()
+            .flat_map(|(address, account)| account.storage.keys().map(|slot| (*address, *slot)))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{EmptyDB, InMemoryDB},
+        primitives::{AccountInfo, Bytecode, TxKind, B256},
+    };
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Condvar, Mutex,
+        },
+        time::Duration,
+    };
+
+    /// Database whose lookups of `senders` wait until all of them are looked up at the same
+    /// time, which only happens if the transactions are executed concurrently.
+    struct Rendezvous {
+        db: InMemoryDB,
+        senders: Vec<Address>,
+        arrived: Mutex<usize>,
+        all_arrived: Condvar,
+        timed_out: AtomicBool,
+    }
+
+    impl DatabaseRef for Rendezvous {
+        type Error = <EmptyDB as DatabaseRef>::Error;
+
+        fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            if self.senders.contains(&address) {
+                let mut arrived = self.arrived.lock().unwrap();
+                *arrived += 1;
+                self.all_arrived.notify_all();
+                let (_arrived, wait) = self
+                    .all_arrived
+                    .wait_timeout_while(arrived, Duration::from_secs(10), |arrived| {
+                        *arrived < self.senders.len()
+                    })
+                    .unwrap();
+                self.timed_out.fetch_or(wait.timed_out(), Ordering::Relaxed);
+            }
+            self.db.basic_ref(address)
+        }
+
+        fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+            self.db.code_by_hash_ref(code_hash)
+        }
+
+        fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+            self.db.storage_ref(address, index)
+        }
+
+        fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+            self.db.block_hash_ref(number)
+        }
+    }
+
+    fn transfer(caller: Address, to: Address) -> TxEnv {
+        TxEnv {
+            caller,
+            transact_to: TxKind::Call(to),
+            value: U256::from(100),
+            gas_limit: 21_000,
+            ..Default::default()
+        }
+    }
+
+    fn funded(accounts: &[Address]) -> InMemoryDB {
+        let mut db = InMemoryDB::default();
+        for address in accounts {
+            db.insert_account_info(*address, AccountInfo::from_balance(U256::from(1_000)));
+        }
+        db
+    }
+
+    #[test]
+    fn independent_transfers_run_concurrently() {
+        let [a, b, c, d] = [0x10, 0x20, 0x30, 0x40].map(Address::with_last_byte);
+        let db = Rendezvous {
+            db: funded(&[a, c]),
+            senders: vec![a, c],
+            arrived: Mutex::new(0),
+            all_arrived: Condvar::new(),
+            timed_out: AtomicBool::new(false),
+        };
+
+        let txs = [transfer(a, b), transfer(c, d)];
+        let results =
+            execute_on_threads(&BlockEnv::default(), SpecId::CANCUN, &txs, &db, 2).unwrap();
+        assert_eq!(*db.arrived.lock().unwrap(), 2);
+        assert!(!db.timed_out.load(Ordering::Relaxed));
+        for (sender, receiver, out) in [(a, b, &results[0]), (c, d, &results[1])] {
+            assert!(out.result.is_success());
+            assert_eq!(out.state[&sender].info.balance, U256::from(900));
+            assert_eq!(out.state[&receiver].info.balance, U256::from(100));
+        }
+    }
+
+    #[test]
+    fn conflicting_transfers_are_serialized() {
+        let [a, b, c] = [0x10, 0x20, 0x30].map(Address::with_last_byte);
+        let db = funded(&[a, c]);
+
+        // both transactions credit `b`, the second one has to see the first credit.
+        let txs = [transfer(a, b), transfer(c, b)];
+        let results = execute_parallel(&BlockEnv::default(), SpecId::CANCUN, &txs, &db).unwrap();
+        assert_eq!(results[0].state[&b].info.balance, U256::from(100));
+        assert_eq!(results[1].state[&b].info.balance, U256::from(200));
+        assert_eq!(results[1].state[&c].info.balance, U256::from(900));
+    }
+
+    #[test]
+    fn transactions_exceeding_block_gas_limit_are_rejected() {
+        let [a, b, c, d] = [0x10, 0x20, 0x30, 0x40].map(Address::with_last_byte);
+        let db = funded(&[a, c]);
+        let block = BlockEnv {
+            gas_limit: U256::from(30_000),
+            ..Default::default()
+        };
+
+        let txs = [transfer(a, b), transfer(c, d)];
+        let err = execute_parallel(&block, SpecId::CANCUN, &txs, &db).unwrap_err();
+        let BlockError::GasLimitReached {
+            index,
+            gas_limit,
+            remaining,
+            included,
+        } = err
+        else {
+            panic!("expected the block gas limit to be reached, got {err:?}");
+        };
+        assert_eq!((index, gas_limit, remaining), (1, 21_000, 9_000));
+
+        // the first transaction fits in the block.
+        let results = execute_parallel(&block, SpecId::CANCUN, &txs[..1], &db).unwrap();
+        assert!(results[0].result.is_success());
+        assert_eq!(included, results);
+    }
+}
@@ -1,2 +1,76 @@
 #[doc(hidden)]
 pub use crate::context::evm_context::test_utils::*;
+
+use crate::{
+    db::{CacheDB, EmptyDB},
+    interpreter::opcode::{CALL, GAS, PUSH1, PUSH20, STOP},
+    primitives::{
+        address, AccountInfo, Address, Bytecode, Bytes, EthereumWiring, HaltReason, ResultAndState,
+        SpecId, TxKind,
+    },
+    Evm,
+};
+use std::{vec, vec::Vec};
+
+/// Address the code of [`evm_with_code`] is deployed at.
+pub const CONTRACT: Address = address!("0000000000000000000000000000000000001000");
+
+/// Caller of the transaction of [`evm_with_code`].
+pub const CALLER: Address = address!("1000000000000000000000000000000000000000");
+
+/// Returns a database with the given legacy code deployed at each address.
+pub fn db_with_code<C: AsRef<[u8]>>(
+    contracts: impl IntoIterator<Item = (Address, C)>,
+) -> CacheDB<EmptyDB> {
+    let mut db = CacheDB::new(EmptyDB::default());
+    for (address, code) in contracts {
+        let code = Bytecode::new_legacy(Bytes::copy_from_slice(code.as_ref()));
+        db.insert_account_info(address, AccountInfo::from_bytecode(code));
+    }
+    db
+}
+
+/// Returns `len` contracts at `0x1000`, `0x2000`, ... that each call the next one, and a
+/// database holding them. The last contract stops.
+pub fn call_chain(len: u64) -> (Vec<Address>, CacheDB<EmptyDB>) {
+    let contracts: Vec<_> = (1..=len)
+        .map(|i| Address::left_padding_from(&(0x1000 * i).to_be_bytes()))
+        .collect();
+    // CALL(gas, next, 0, 0, 0, 0, 0)
+    let codes = contracts.iter().enumerate().map(|(i, address)| {
+        let code = match contracts.get(i + 1) {
+            Some(next) => {
+                let mut code = vec![PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH1, 0, PUSH20];
+                code.extend_from_slice(next.as_slice());
+                code.extend_from_slice(&[GAS, CALL, STOP]);
+                code
+            }
+            None => vec![STOP],
+        };
+        (*address, code)
+    });
+    let db = db_with_code(codes);
+    (contracts, db)
+}
+
+/// Returns an EVM running `spec_id` whose transaction calls the legacy `code` deployed at
+/// [`CONTRACT`] from [`CALLER`].
+pub fn evm_with_code(
+    code: &[u8],
+    spec_id: SpecId,
+) -> Evm<'static, EthereumWiring<CacheDB<EmptyDB>, ()>> {
+    Evm::<EthereumWiring<CacheDB<EmptyDB>, ()>>::builder()
+        .with_db(db_with_code([(CONTRACT, code)]))
+        .with_default_ext_ctx()
+        .with_spec_id(spec_id)
+        .modify_tx_env(|tx| {
+            tx.caller = CALLER;
+            tx.transact_to = TxKind::Call(CONTRACT);
+        })
+        .build()
+}
+
+/// Deploys the legacy `code` at [`CONTRACT`] and calls it from [`CALLER`] in `spec_id`.
+pub fn deploy_and_call(code: &[u8], spec_id: SpecId) -> ResultAndState<HaltReason> {
+    evm_with_code(code, spec_id).transact().unwrap()
+}